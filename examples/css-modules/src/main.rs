@@ -12,6 +12,12 @@ mod green {
     pub use class::*;
 }
 
+mod emphasis {
+    silkenweb::css!(path = "emphasis.css", auto_mount, transpile = (modules));
+
+    pub use class::*;
+}
+
 fn main() {
     log_panics();
 
@@ -20,6 +26,8 @@ fn main() {
     let app = div().children([
         div().class(red::color()).text("Red text"),
         div().class(green::color()).text("Green text"),
+        // `emphasis` composes `color` from "red.css", so it has both class names.
+        div().classes(emphasis::emphasis()).text("Bold red text"),
     ]);
     mount("app", app);
 }
@@ -11,6 +11,9 @@ fn main() {
     let snippet = inline_html!(r#"<p>This is an HTML snippet</p>"#);
     mount(
         "app",
-        div().children([snippet, test_image(), svg::test_image()]),
+        div()
+            .child(snippet)
+            .child(test_image())
+            .child(svg::test_image()),
     );
 }
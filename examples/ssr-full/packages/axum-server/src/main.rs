@@ -1,24 +1,28 @@
-use std::io;
+use std::{io, time::Duration};
 
 use axum::{
     error_handling::HandleError,
     http::{StatusCode, Uri},
-    response::{IntoResponse, Response},
+    response::{Html, IntoResponse, Response},
     Extension, Router, Server,
 };
-use silkenweb::{document::Document, dom::Dry, router, task};
+use silkenweb::{
+    document::Document,
+    dom::Dry,
+    router,
+    task::{self, server::Renderer},
+};
 use ssr_full_app::app;
-use tokio_util::task::LocalPoolHandle;
 use tower_http::services::ServeDir;
 
 #[tokio::main]
 async fn main() {
-    let local_pool = LocalPoolHandle::new(16);
+    let renderer = Renderer::new(16).timeout(Duration::from_secs(10));
     let pkg_service = HandleError::new(ServeDir::new("../axum-client/pkg"), io_error_to_response);
     let app = Router::new()
         .nest_service("/pkg", pkg_service)
         .fallback(handler)
-        .layer(Extension(local_pool));
+        .layer(Extension(renderer));
     Server::bind(&"0.0.0.0:8080".parse().unwrap())
         .serve(app.into_make_service())
         .await
@@ -29,23 +33,23 @@ async fn io_error_to_response(err: io::Error) -> impl IntoResponse {
     (StatusCode::NOT_FOUND, err.to_string())
 }
 
-async fn handler(Extension(local_pool): Extension<LocalPoolHandle>, uri: Uri) -> impl IntoResponse {
+async fn handler(Extension(renderer): Extension<Renderer>, uri: Uri) -> Response {
     // Axum requires futures to be `Send` so they can be moved between threads.
-    // Silkenweb is single threaded, so we spawn a task pinned to a thread using
-    // `LocalPoolHandle`.
-    local_pool
-        .spawn_pinned(|| task::scope(render(uri)))
-        .await
-        .unwrap()
+    // Silkenweb is single threaded, so `Renderer` renders each page on a thread
+    // local executor, with its own router and document head.
+    match renderer.render(move || render(uri)).await {
+        Ok(page_html) => Html(page_html).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
 }
 
-async fn render(uri: Uri) -> impl IntoResponse {
+async fn render(uri: Uri) -> String {
     let (head, body) = app::<Dry>();
     Dry::mount_in_head("head", head);
     router::set_url_path(uri.path());
     task::render_now().await;
 
-    let page_html = format!(
+    format!(
         include_str!("../../app/page.tmpl.html"),
         head_html = Dry::head_inner_html(),
         body_html = body.freeze(),
@@ -53,10 +57,5 @@ async fn render(uri: Uri) -> impl IntoResponse {
             import init, {js_main} from '/pkg/ssr_full_axum_client.js';
             init().then(js_main);
         "#
-    );
-
-    Response::builder()
-        .status(StatusCode::OK)
-        .body(page_html)
-        .unwrap()
+    )
 }
@@ -2,16 +2,21 @@ use std::time::Duration;
 
 use futures::stream::StreamExt;
 use futures_signals::signal::{Mutable, SignalExt};
-use silkenweb::{cfg_browser, elements::html::*, prelude::*, task::spawn_local, time, value::Sig};
+use silkenweb::{
+    cfg_browser,
+    elements::html::*,
+    prelude::*,
+    task::spawn_local,
+    time::{self, TimeSignal},
+    value::Sig,
+};
 
 fn app() -> Div {
     let ticks = Mutable::new(0);
     // Tick every tenth of a second.
     let mut interval = time::interval(Duration::from_millis(100));
     // Throttle ticks so they only happen every second.
-    let ticks_signal = ticks
-        .signal()
-        .throttle(|| time::sleep(Duration::from_secs(1)));
+    let ticks_signal = ticks.signal().throttle_for(Duration::from_secs(1));
 
     spawn_local(async move {
         while interval.next().await.is_some() {
@@ -0,0 +1,91 @@
+//! Bundle `css!` stylesheets into a single CSS file.
+//!
+//! A [`Bundle`] combines stylesheets into a single CSS file, with a content
+//! hash in the filename so it can be cached by browsers. It's built
+//! explicitly, usually in a build script, from the same files and with the
+//! same options as the `css!` invocations it replaces. Those invocations
+//! should be marked `bundled`, so they aren't mounted at runtime. Their
+//! `stylesheet::text` is still available, but isn't linked into the binary
+//! unless it's used.
+//!
+//! ```no_run
+//! // build.rs
+//! use std::env;
+//!
+//! use silkenweb_css::{bundle::Bundle, Css};
+//!
+//! let mut bundle = Bundle::new();
+//!
+//! for path in ["src/base.css", "src/app.css"] {
+//!     println!("cargo:rerun-if-changed={path}");
+//!     bundle.add(&Css::from_path(path, None).unwrap());
+//! }
+//!
+//! let path = bundle
+//!     .write(env::var("OUT_DIR").unwrap(), "styles")
+//!     .unwrap();
+//! ```
+//!
+//! Scoped stylesheets must be scoped in the same way as `css!` scopes them,
+//! with `css.scope(&css.scope_attribute())`, after any transpilation.
+//!
+//! # Ordering
+//!
+//! Stylesheets are bundled in the order they're added. Identical stylesheets
+//! are only included once, where they're first added.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{fnv1a, Css, Error};
+
+/// A bundle of stylesheets, in the order they were added.
+#[derive(Clone, Debug, Default)]
+pub struct Bundle {
+    stylesheets: Vec<String>,
+}
+
+impl Bundle {
+    /// An empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `css` to the end of the bundle, unless it's already included.
+    pub fn add(&mut self, css: &Css) -> &mut Self {
+        let content = css.content();
+
+        if !self.stylesheets.iter().any(|existing| existing == content) {
+            self.stylesheets.push(content.to_string());
+        }
+
+        self
+    }
+
+    /// The bundled CSS.
+    pub fn content(&self) -> String {
+        self.stylesheets
+            .iter()
+            .map(|stylesheet| format!("{stylesheet}\n"))
+            .collect()
+    }
+
+    /// Write the bundle to `out_dir/{name}-{hash}.css`.
+    ///
+    /// `hash` is derived from the content. The path of the bundle is
+    /// returned.
+    pub fn write(&self, out_dir: impl AsRef<Path>, name: &str) -> Result<PathBuf, Error> {
+        let content = self.content();
+        let out_dir = out_dir.as_ref();
+        fs::create_dir_all(out_dir)
+            .with_context(|| format!("Couldn't create directory '{}'", out_dir.display()))?;
+        let path = out_dir.join(format!("{name}-{:08x}.css", fnv1a(content.as_bytes())));
+        fs::write(&path, content)
+            .with_context(|| format!("Couldn't write file '{}'", path.display()))?;
+
+        Ok(path)
+    }
+}
@@ -3,14 +3,18 @@ use std::{
     env,
     ffi::OsStr,
     fs,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     str::FromStr,
 };
 
 use anyhow::{anyhow, Context};
-use cssparser::{Parser, ParserInput, Token};
+use cssparser::{Delimiter, Parser, ParserInput, Token};
 use derive_more::Into;
 use grass::InputSyntax;
+use itertools::Itertools;
+
+pub mod bundle;
+pub mod report;
 
 #[cfg_attr(feature = "css-transpile", path = "transpile-enabled.rs")]
 #[cfg_attr(not(feature = "css-transpile"), path = "transpile-disabled.rs")]
@@ -22,6 +26,19 @@ pub use transpile::Version;
 pub struct NameMapping {
     pub plain: String,
     pub mangled: String,
+    /// Any other class names from CSS Modules `composes`.
+    pub composes: Vec<String>,
+}
+
+/// A custom property registered with an `@property` rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyRule {
+    /// The property name, without the `--` prefix.
+    pub name: String,
+    /// The `syntax` descriptor, without quotes.
+    pub syntax: Option<String>,
+    /// The `initial-value` descriptor.
+    pub initial_value: Option<String>,
 }
 
 #[derive(Into)]
@@ -62,6 +79,7 @@ impl CssSyntax {
 pub struct Css {
     content: String,
     dependency: Option<String>,
+    composed_dependencies: Vec<String>,
 }
 
 impl Css {
@@ -69,6 +87,7 @@ impl Css {
         Ok(Self {
             content: Self::css_content(content.into(), syntax)?,
             dependency: None,
+            composed_dependencies: Vec::new(),
         })
     }
 
@@ -78,11 +97,7 @@ impl Css {
 
         let root_dir = env::var(CARGO_MANIFEST_DIR)
             .with_context(|| format!("Couldn't read '{CARGO_MANIFEST_DIR}' variable"))?;
-        let path = PathBuf::from(root_dir)
-            .join(path)
-            .into_os_string()
-            .into_string()
-            .map_err(|filename| anyhow!("Couldn't convert filename to string: '{filename:?}'"))?;
+        let path = normalized_path(&PathBuf::from(root_dir).join(path))?;
 
         Ok(Self {
             content: Self::css_content(
@@ -91,6 +106,7 @@ impl Css {
                 syntax,
             )?,
             dependency: Some(path),
+            composed_dependencies: Vec::new(),
         })
     }
 
@@ -123,6 +139,15 @@ impl Css {
         self.dependency.as_deref()
     }
 
+    /// All the files this stylesheet depends on, including files referenced
+    /// by CSS Modules `composes`.
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.dependency
+            .iter()
+            .chain(&self.composed_dependencies)
+            .map(String::as_str)
+    }
+
     pub fn content(&self) -> &str {
         &self.content
     }
@@ -164,6 +189,245 @@ impl Css {
 
         variables.into_iter()
     }
+
+    /// An attribute name to scope this stylesheet, derived from its content.
+    pub fn scope_attribute(&self) -> String {
+        format!(
+            "data-silkenweb-scope-{:08x}",
+            fnv1a(self.content.as_bytes())
+        )
+    }
+
+    /// Scope all style rules, so they only apply to elements with `attribute`.
+    ///
+    /// `attribute` is added to the last compound selector of each selector,
+    /// before any pseudo-element. Rules inside `@media`, `@supports`,
+    /// `@layer`, `@container` and `@document` are scoped. Other at-rules are
+    /// left as they are.
+    pub fn scope(&mut self, attribute: &str) {
+        let scoped = {
+            let mut parser_input = ParserInput::new(&self.content);
+            let mut input = Parser::new(&mut parser_input);
+            let mut scoped = String::new();
+            scope_rules(&mut input, &format!("[{attribute}]"), &mut scoped);
+            scoped
+        };
+
+        self.content = scoped;
+    }
+
+    /// The custom properties registered with top level `@property` rules.
+    pub fn property_rules(&self) -> Vec<PropertyRule> {
+        let mut parser_input = ParserInput::new(&self.content);
+        let mut input = Parser::new(&mut parser_input);
+        let mut rules = Vec::new();
+
+        while let Ok(token) = input.next() {
+            let Token::AtKeyword(keyword) = token else {
+                continue;
+            };
+
+            if !keyword.eq_ignore_ascii_case("property") {
+                continue;
+            }
+
+            let Ok(Token::Ident(name)) = input.next() else {
+                continue;
+            };
+
+            let Some(name) = name.strip_prefix("--").map(str::to_string) else {
+                continue;
+            };
+
+            if let Ok(Token::CurlyBracketBlock) = input.next() {
+                let rule = input
+                    .parse_nested_block(|block| {
+                        Ok::<_, cssparser::ParseError<()>>(property_rule(name, block))
+                    })
+                    .unwrap();
+                rules.push(rule);
+            }
+        }
+
+        rules
+    }
+}
+
+fn scope_rules(input: &mut Parser, scope: &str, output: &mut String) {
+    loop {
+        let start = input.position();
+        let mut at_rule = None;
+        let mut is_first_token = true;
+
+        loop {
+            let prelude_end = input.position();
+            let Ok(token) = input.next_including_whitespace_and_comments() else {
+                output.push_str(input.slice_from(start));
+                return;
+            };
+
+            match token {
+                Token::WhiteSpace(_) | Token::Comment(_) => continue,
+                Token::AtKeyword(name) if is_first_token => {
+                    at_rule = Some(name.to_ascii_lowercase())
+                }
+                Token::Semicolon => {
+                    output.push_str(input.slice_from(start));
+                    break;
+                }
+                Token::CurlyBracketBlock => {
+                    let prelude = input.slice(start..prelude_end);
+
+                    match at_rule.as_deref() {
+                        Some("media" | "supports" | "layer" | "container" | "document") => {
+                            output.push_str(prelude);
+                            output.push('{');
+                            input
+                                .parse_nested_block(|block| {
+                                    scope_rules(block, scope, output);
+                                    Ok::<_, cssparser::ParseError<()>>(())
+                                })
+                                .unwrap();
+                            output.push('}');
+                        }
+                        Some(_) => {
+                            input
+                                .parse_nested_block(|block| {
+                                    while block.next_including_whitespace_and_comments().is_ok() {}
+                                    Ok::<_, cssparser::ParseError<()>>(())
+                                })
+                                .unwrap();
+                            output.push_str(input.slice_from(start));
+                        }
+                        None => {
+                            let body = input
+                                .parse_nested_block(|block| {
+                                    let body_start = block.position();
+                                    while block.next_including_whitespace_and_comments().is_ok() {}
+                                    Ok::<_, cssparser::ParseError<()>>(block.slice_from(body_start))
+                                })
+                                .unwrap();
+                            output.push_str(&scope_selectors(prelude, scope));
+                            output.push('{');
+                            output.push_str(body);
+                            output.push('}');
+                        }
+                    }
+
+                    break;
+                }
+                _ => (),
+            }
+
+            is_first_token = false;
+        }
+    }
+}
+
+/// Add `scope` to each selector in a comma separated list.
+fn scope_selectors(selectors: &str, scope: &str) -> String {
+    split_top_level(selectors, ',')
+        .into_iter()
+        .map(|selector| scope_selector(selector.trim(), scope))
+        .join(", ")
+}
+
+/// Add `scope` to the last compound selector, before any pseudo-element.
+fn scope_selector(selector: &str, scope: &str) -> String {
+    const LEGACY_PSEUDO_ELEMENTS: [&str; 4] = [":before", ":after", ":first-line", ":first-letter"];
+    let mut depth = 0;
+    let mut pseudo_element = None;
+
+    for (index, c) in selector.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            _ if depth > 0 => (),
+            ' ' | '\t' | '\n' | '>' | '+' | '~' => pseudo_element = None,
+            ':' if pseudo_element.is_none() => {
+                let rest = selector[index..].to_ascii_lowercase();
+
+                if rest.starts_with("::")
+                    || LEGACY_PSEUDO_ELEMENTS
+                        .iter()
+                        .any(|pseudo| rest.starts_with(pseudo))
+                {
+                    pseudo_element = Some(index);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let insert_at = pseudo_element.unwrap_or(selector.len());
+
+    format!(
+        "{}{scope}{}",
+        &selector[..insert_at],
+        &selector[insert_at..]
+    )
+}
+
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut parts = Vec::new();
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => (),
+        }
+    }
+
+    parts.push(&text[start..]);
+    parts
+}
+
+fn property_rule(name: String, block: &mut Parser) -> PropertyRule {
+    let mut rule = PropertyRule {
+        name,
+        syntax: None,
+        initial_value: None,
+    };
+
+    while let Ok(token) = block.next() {
+        let Token::Ident(descriptor) = token else {
+            continue;
+        };
+        let descriptor = descriptor.to_ascii_lowercase();
+
+        if block.expect_colon().is_err() {
+            continue;
+        }
+
+        let start = block.position();
+        let _ = block.parse_until_before(Delimiter::Semicolon, |value| {
+            while value.next().is_ok() {}
+            Ok::<_, cssparser::ParseError<()>>(())
+        });
+        let value = block.slice_from(start).trim().to_string();
+
+        match descriptor.as_str() {
+            "syntax" => {
+                rule.syntax = Some(
+                    value
+                        .trim_matches(|c| c == '\'' || c == '"')
+                        .trim()
+                        .to_string(),
+                )
+            }
+            "initial-value" => rule.initial_value = Some(value),
+            _ => (),
+        }
+    }
+
+    rule
 }
 
 pub struct Transpile {
@@ -205,6 +469,37 @@ impl From<anyhow::Error> for TranspileError {
     }
 }
 
+/// Normalize `path` by removing `.` and `..` components.
+///
+/// CSS Modules class names are derived from the filename, so files must always
+/// be referred to by the same path. This doesn't touch the file system, so
+/// paths are the same whether or not they go through symlinks.
+fn normalized_path(path: &Path) -> anyhow::Result<String> {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+        .into_os_string()
+        .into_string()
+        .map_err(|filename| anyhow!("Couldn't convert filename to string: '{filename:?}'"))
+}
+
+/// FNV-1a, as hashes must be the same for server and browser builds.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
 fn flattened_tokens<'i>(tokens: &mut Vec<Token<'i>>, input: &mut Parser<'i, '_>) {
     while let Ok(token) = input.next_including_whitespace_and_comments() {
         tokens.push(token.clone());
@@ -0,0 +1,494 @@
+//! Report dead and undefined CSS classes.
+//!
+//! [`ClassUsage`] cross references the classes defined in stylesheets with
+//! the Rust source of a crate. A class is used if the constant or function
+//! that `css!` generates for it is referenced, or its name is in a string
+//! literal. A class is undefined if it's passed as a string literal to
+//! `.class(...)` or `.classes(...)`, but isn't defined by any stylesheet.
+//!
+//! The analysis is textual, so it doesn't know which stylesheet a constant
+//! refers to. It's intended to be run from a build script:
+//!
+//! ```no_run
+//! // build.rs
+//! use silkenweb_css::{report::ClassUsage, Css};
+//!
+//! let mut usage = ClassUsage::new();
+//! usage.stylesheet(&Css::from_path("src/app.css", None).unwrap(), None);
+//! usage.source_dir("src").unwrap();
+//! usage.report().emit_warnings();
+//! ```
+//!
+//! # Limitations
+//!
+//! The source isn't compiled or macro expanded, so the report is approximate.
+//!
+//! A class may be reported as used when it isn't, if:
+//!
+//! - Any identifier has the same name as its constant or function, even if it
+//!   refers to something else, like a local variable called `button`.
+//! - It appears as a word in any string literal, even one that isn't a class.
+//!
+//! A class may be reported as dead when it's used, if:
+//!
+//! - Its name is built at runtime or by a macro, for example with `format!`,
+//!   `concat!` or `paste!`.
+//! - It's only used from source that isn't scanned, like another crate.
+//!
+//! For undefined classes, every word in a string literal passed to `.class`
+//! or `.classes` is treated as a class name, including literals passed through
+//! macros like `format!` (format strings containing `{}` are ignored). Class
+//! names that aren't string literals in the call, like variables, aren't
+//! checked.
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt, fs,
+    iter::Peekable,
+    path::Path,
+    str::Chars,
+};
+
+use anyhow::Context;
+
+use crate::{Css, Error};
+
+/// Collect class definitions and usage.
+#[derive(Default)]
+pub struct ClassUsage {
+    defined: Vec<DefinedClass>,
+    identifiers: HashSet<String>,
+    strings: HashSet<String>,
+    class_arguments: BTreeSet<String>,
+}
+
+struct DefinedClass {
+    name: String,
+    ident: String,
+}
+
+impl ClassUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the classes defined by `css`.
+    ///
+    /// `prefix` should be the same as the `prefix` parameter of `css!`.
+    pub fn stylesheet(&mut self, css: &Css, prefix: Option<&str>) -> &mut Self {
+        for name in css.class_names() {
+            let plain = match prefix {
+                Some(prefix) => match name.strip_prefix(prefix) {
+                    Some(plain) => plain,
+                    None => continue,
+                },
+                None => &name,
+            };
+            let ident = plain.replace(|c: char| !c.is_alphanumeric(), "_");
+
+            self.defined.push(DefinedClass { name, ident });
+        }
+
+        self
+    }
+
+    /// Scan Rust source for class usage.
+    pub fn source(&mut self, source: &str) -> &mut Self {
+        let tokens = tokenize(source);
+
+        for (index, token) in tokens.iter().enumerate() {
+            match token {
+                RustToken::Ident(ident) => {
+                    self.identifiers.insert(ident.clone());
+
+                    if (ident == "class" || ident == "classes")
+                        && index > 0
+                        && tokens[index - 1] == RustToken::Punct('.')
+                        && tokens.get(index + 1) == Some(&RustToken::Punct('('))
+                    {
+                        self.class_arguments
+                            .extend(string_arguments(&tokens[index + 1..]));
+                    }
+                }
+                RustToken::Str(string) => {
+                    self.strings
+                        .extend(string.split_whitespace().map(str::to_string));
+                }
+                RustToken::Punct(_) => (),
+            }
+        }
+
+        self
+    }
+
+    /// Scan all `.rs` files in `dir`, recursively, for class usage.
+    pub fn source_dir(&mut self, dir: impl AsRef<Path>) -> Result<&mut Self, Error> {
+        let dir = dir.as_ref();
+
+        for entry in fs::read_dir(dir)
+            .with_context(|| format!("Couldn't read directory '{}'", dir.display()))?
+        {
+            let path = entry
+                .with_context(|| format!("Couldn't read directory '{}'", dir.display()))?
+                .path();
+
+            if path.is_dir() {
+                self.source_dir(&path)?;
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let source = fs::read_to_string(&path)
+                    .with_context(|| format!("Couldn't read file '{}'", path.display()))?;
+                self.source(&source);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Generate a report from the stylesheets and source scanned so far.
+    pub fn report(&self) -> ClassReport {
+        let dead = self
+            .defined
+            .iter()
+            .filter(|class| {
+                !self.identifiers.contains(&class.ident.to_uppercase())
+                    && !self.identifiers.contains(&class.ident.to_lowercase())
+                    && !self.strings.contains(&class.name)
+            })
+            .map(|class| class.name.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let undefined = self
+            .class_arguments
+            .iter()
+            .filter(|name| !self.defined.iter().any(|class| &&class.name == name))
+            .cloned()
+            .collect();
+
+        ClassReport { dead, undefined }
+    }
+}
+
+/// Dead and undefined classes, from [`ClassUsage::report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassReport {
+    /// Classes that are defined in a stylesheet, but never used.
+    pub dead: Vec<String>,
+    /// Classes that are used, but not defined in any stylesheet.
+    pub undefined: Vec<String>,
+}
+
+impl ClassReport {
+    pub fn is_empty(&self) -> bool {
+        self.dead.is_empty() && self.undefined.is_empty()
+    }
+
+    /// Print a `cargo:warning` for each dead or undefined class.
+    ///
+    /// This should only be called from a build script.
+    pub fn emit_warnings(&self) {
+        for line in self.lines() {
+            println!("cargo:warning={line}");
+        }
+    }
+
+    fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.dead
+            .iter()
+            .map(|class| format!("CSS class '{class}' is never used"))
+            .chain(
+                self.undefined
+                    .iter()
+                    .map(|class| format!("CSS class '{class}' is not defined")),
+            )
+    }
+}
+
+impl fmt::Display for ClassReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.lines() {
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RustToken {
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+/// The class names in string literals in the parenthesized arguments at the
+/// start of `tokens`.
+fn string_arguments(tokens: &[RustToken]) -> Vec<String> {
+    let mut depth = 0;
+    let mut strings = Vec::new();
+
+    for token in tokens {
+        match token {
+            RustToken::Punct('(' | '[' | '{') => depth += 1,
+            RustToken::Punct(')' | ']' | '}') => {
+                depth -= 1;
+
+                if depth == 0 {
+                    break;
+                }
+            }
+            RustToken::Str(string) => strings.extend(
+                string
+                    .split_whitespace()
+                    .filter(|class| {
+                        class
+                            .chars()
+                            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+                    })
+                    .map(str::to_string),
+            ),
+            _ => (),
+        }
+    }
+
+    strings
+}
+
+/// A rough tokenizer for Rust source.
+///
+/// It only needs to find identifiers and string literals, so numbers are
+/// treated as identifiers and escapes aren't processed.
+fn tokenize(source: &str) -> Vec<RustToken> {
+    let mut chars = source.chars().peekable();
+    let mut tokens = Vec::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                skip_block_comment(&mut chars);
+            }
+            '"' => tokens.push(RustToken::Str(string_literal(&mut chars))),
+            'r' if matches!(chars.peek(), Some('"' | '#')) => {
+                let mut hashes = 0;
+
+                while chars.next_if_eq(&'#').is_some() {
+                    hashes += 1;
+                }
+
+                if chars.next_if_eq(&'"').is_some() {
+                    tokens.push(RustToken::Str(raw_string_literal(&mut chars, hashes)));
+                } else {
+                    // A raw identifier
+                    tokens.push(RustToken::Ident(identifier(&mut chars, String::new())));
+                }
+            }
+            '\'' => {
+                let mut lookahead = chars.clone();
+                let is_char = match lookahead.next() {
+                    Some('\\') => true,
+                    Some(_) => lookahead.next() == Some('\''),
+                    None => false,
+                };
+
+                if is_char {
+                    if chars.next() == Some('\\') {
+                        chars.next();
+                    }
+
+                    while chars.next().is_some_and(|c| c != '\'') {}
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                tokens.push(RustToken::Ident(identifier(&mut chars, c.to_string())))
+            }
+            c if c.is_whitespace() => (),
+            c => tokens.push(RustToken::Punct(c)),
+        }
+    }
+
+    tokens
+}
+
+fn skip_block_comment(chars: &mut Peekable<Chars>) {
+    let mut depth = 1;
+
+    while depth > 0 {
+        match chars.next() {
+            Some('/') if chars.next_if_eq(&'*').is_some() => depth += 1,
+            Some('*') if chars.next_if_eq(&'/').is_some() => depth -= 1,
+            Some(_) => (),
+            None => break,
+        }
+    }
+}
+
+fn string_literal(chars: &mut Peekable<Chars>) -> String {
+    let mut string = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => break,
+            c => string.push(c),
+        }
+    }
+
+    string
+}
+
+fn raw_string_literal(chars: &mut Peekable<Chars>, hashes: usize) -> String {
+    let mut string = String::new();
+
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            let mut lookahead = chars.clone();
+
+            if (0..hashes).all(|_| lookahead.next() == Some('#')) {
+                *chars = lookahead;
+                break;
+            }
+        }
+
+        string.push(c);
+    }
+
+    string
+}
+
+fn identifier(chars: &mut Peekable<Chars>, mut ident: String) -> String {
+    while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+        ident.push(c);
+    }
+
+    ident
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, ClassUsage, RustToken};
+    use crate::{Css, CssSyntax};
+
+    fn strings(source: &str) -> Vec<String> {
+        tokenize(source)
+            .into_iter()
+            .filter_map(|token| match token {
+                RustToken::Str(string) => Some(string),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn ident(ident: &str) -> RustToken {
+        RustToken::Ident(ident.to_string())
+    }
+
+    fn string(string: &str) -> RustToken {
+        RustToken::Str(string.to_string())
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(
+            strings("// \"line\"\n/* \"block\" /* \"nested\" */ \"still block\" */ \"code\""),
+            ["code"]
+        );
+        assert!(tokenize("/* unterminated \"comment\"").is_empty());
+    }
+
+    #[test]
+    fn escapes_are_dropped() {
+        assert_eq!(strings(r#""a \"quoted\" b" "c""#), ["a quoted b", "c"]);
+    }
+
+    #[test]
+    fn raw_strings() {
+        assert_eq!(
+            tokenize(r###"r#"a "quoted" b"# r"plain" r##"a "# b"## r#type x"###),
+            [
+                string(r#"a "quoted" b"#),
+                string("plain"),
+                string(r##"a "# b"##),
+                ident("type"),
+                ident("x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lifetimes_and_chars() {
+        assert_eq!(
+            tokenize(r#"fn f<'a>(x: &'static str) -> char { '"' }"#),
+            [
+                ident("fn"),
+                ident("f"),
+                RustToken::Punct('<'),
+                ident("a"),
+                RustToken::Punct('>'),
+                RustToken::Punct('('),
+                ident("x"),
+                RustToken::Punct(':'),
+                RustToken::Punct('&'),
+                ident("static"),
+                ident("str"),
+                RustToken::Punct(')'),
+                RustToken::Punct('-'),
+                RustToken::Punct('>'),
+                ident("char"),
+                RustToken::Punct('{'),
+                RustToken::Punct('}'),
+            ]
+        );
+        assert_eq!(
+            strings(r#"let quote = '\''; let unicode = '\u{22}'; let s = "after";"#),
+            ["after"]
+        );
+    }
+
+    #[test]
+    fn nested_macros() {
+        let report = ClassUsage::new()
+            .source(
+                r#"
+                    div()
+                        .class(format!("{}-{}", "a", concat!("b", "c")))
+                        .classes(vec!["d e"])
+                        .text("not-a-class");
+                "#,
+            )
+            .report();
+
+        assert_eq!(report.undefined, ["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn dead_classes() {
+        let css = Css::from_content(
+            ".used-const, .used-fn, .in-string, .dead { color: red }",
+            CssSyntax::default(),
+        )
+        .unwrap();
+        let report = ClassUsage::new()
+            .stylesheet(&css, None)
+            .source(
+                r#"
+                    div()
+                        .class(class::USED_CONST)
+                        .class(class::used_fn())
+                        .class("in-string undefined");
+                "#,
+            )
+            .report();
+
+        assert_eq!(report.dead, ["dead"]);
+        assert_eq!(report.undefined, ["undefined"]);
+    }
+}
@@ -1,5 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
+    env, fs,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
 };
 
@@ -7,13 +9,13 @@ use anyhow::{anyhow, Context, Error};
 use clonelet::clone;
 use itertools::Itertools;
 use lightningcss::{
-    css_modules::{self, CssModuleExport},
+    css_modules::{self, CssModuleExport, CssModuleReference},
     stylesheet::{MinifyOptions, ParserFlags, ParserOptions, PrinterOptions, StyleSheet},
     targets::{Features, Targets},
 };
 
-use super::{Browsers, Css, NameMapping, Transpile};
-use crate::TranspileError;
+use super::{Browsers, Css, CssSyntax, NameMapping, Transpile};
+use crate::{normalized_path, TranspileError};
 
 pub struct Version {
     major: u8,
@@ -132,26 +134,155 @@ pub fn transpile(
             .context("CSS printing failed")?;
         source.content = css.code;
 
-        return css.exports.map(name_mappings).transpose();
+        let Some(exports) = css.exports else {
+            return Ok(None);
+        };
+        let mut resolver = ComposesResolver::new(flags);
+        let name_mappings = resolver.name_mappings(source.dependency.as_deref(), &exports)?;
+        source.composed_dependencies = resolver.dependencies;
+
+        return Ok(Some(name_mappings));
     }
 
     Ok(None)
 }
 
-fn name_mappings(
-    exports: HashMap<String, CssModuleExport>,
-) -> Result<Vec<NameMapping>, TranspileError> {
-    exports
-        .into_iter()
-        .map(|(plain, mapping)| {
-            if mapping.composes.is_empty() {
+/// Expand `composes` into the full list of class names.
+///
+/// `composes` can reference classes from the same file, global classes, or
+/// classes from other files. Classes from other files are mangled as if the
+/// file was used in a separate `css!` invocation with `modules` enabled. It's
+/// up to the user to make sure the other file's stylesheet is mounted.
+struct ComposesResolver {
+    flags: ParserFlags,
+    dependency_exports: HashMap<String, HashMap<String, CssModuleExport>>,
+    dependencies: Vec<String>,
+}
+
+impl ComposesResolver {
+    fn new(flags: ParserFlags) -> Self {
+        Self {
+            flags,
+            dependency_exports: HashMap::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    fn name_mappings(
+        &mut self,
+        filename: Option<&str>,
+        exports: &HashMap<String, CssModuleExport>,
+    ) -> Result<Vec<NameMapping>, Error> {
+        exports
+            .iter()
+            .map(|(plain, export)| {
+                let mut names = vec![export.name.clone()];
+                self.composes(filename, exports, export, &mut names)?;
+                let mangled = names.remove(0);
+
                 Ok(NameMapping {
-                    plain,
-                    mangled: mapping.name,
+                    plain: plain.clone(),
+                    mangled,
+                    composes: names,
                 })
-            } else {
-                Err(Error::msg("`composes` is unsupported").into())
+            })
+            .collect()
+    }
+
+    fn composes(
+        &mut self,
+        filename: Option<&str>,
+        exports: &HashMap<String, CssModuleExport>,
+        export: &CssModuleExport,
+        names: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        for reference in &export.composes {
+            match reference {
+                CssModuleReference::Local { name } => {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+
+                        if let Some(export) = exports.values().find(|export| &export.name == name) {
+                            self.composes(filename, exports, export, names)?;
+                        }
+                    }
+                }
+                CssModuleReference::Global { name } => {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+                CssModuleReference::Dependency { name, specifier } => {
+                    let dependency = dependency_path(filename, specifier)?;
+                    let exports = self.dependency_exports(&dependency)?;
+                    let export = exports
+                        .get(name)
+                        .ok_or_else(|| anyhow!("Class '{name}' not found in '{dependency}'"))?;
+
+                    if !names.contains(&export.name) {
+                        names.push(export.name.clone());
+                        self.composes(Some(&dependency), &exports, export, names)?;
+                    }
+                }
             }
-        })
-        .collect()
+        }
+
+        Ok(())
+    }
+
+    fn dependency_exports(
+        &mut self,
+        filename: &str,
+    ) -> Result<HashMap<String, CssModuleExport>, Error> {
+        if let Some(exports) = self.dependency_exports.get(filename) {
+            return Ok(exports.clone());
+        }
+
+        let content = fs::read_to_string(filename)
+            .with_context(|| format!("Couldn't read file '{filename}'"))?;
+        let content = Css::css_content(content, CssSyntax::from_path(filename))?;
+        let stylesheet = StyleSheet::parse(
+            &content,
+            ParserOptions {
+                filename: filename.to_string(),
+                css_modules: Some(css_modules::Config {
+                    pattern: css_modules::Pattern::default(),
+                    dashed_idents: false,
+                }),
+                source_index: 0,
+                error_recovery: true,
+                warnings: None,
+                flags: self.flags,
+            },
+        )
+        .map_err(|e| anyhow!("Parsing '{filename}' failed: {e}"))?;
+        let exports = stylesheet
+            .to_css(PrinterOptions::default())
+            .with_context(|| format!("CSS printing failed for '{filename}'"))?
+            .exports
+            .unwrap_or_default();
+
+        self.dependencies.push(filename.to_string());
+        self.dependency_exports
+            .insert(filename.to_string(), exports.clone());
+        Ok(exports)
+    }
+}
+
+/// The path of a `composes` dependency.
+///
+/// `specifier` is relative to the directory of `filename`, or
+/// `CARGO_MANIFEST_DIR` for inline content. The path is normalized the same way
+/// as [`Css::from_path`], so the dependency's class names are the same as if
+/// it was used with `css!`.
+fn dependency_path(filename: Option<&str>, specifier: &str) -> Result<String, Error> {
+    let dir = if let Some(filename) = filename {
+        Path::new(filename)
+            .parent()
+            .map_or_else(PathBuf::new, Path::to_path_buf)
+    } else {
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").context("Couldn't read 'CARGO_MANIFEST_DIR'")?)
+    };
+
+    normalized_path(&dir.join(specifier))
 }
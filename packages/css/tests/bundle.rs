@@ -0,0 +1,77 @@
+use std::{fs, path::Path};
+
+use silkenweb_css::{bundle::Bundle, Css, CssSyntax};
+
+fn css(content: &str) -> Css {
+    Css::from_content(content, CssSyntax::default()).unwrap()
+}
+
+#[test]
+fn insertion_order() {
+    let mut bundle = Bundle::new();
+
+    // These are in reverse order of their content hashes.
+    for content in [
+        ".z { color: red }",
+        ".a { color: blue }",
+        ".m { color: green }",
+    ] {
+        bundle.add(&css(content));
+    }
+
+    assert_eq!(
+        bundle.content(),
+        ".z { color: red }\n.a { color: blue }\n.m { color: green }\n"
+    );
+}
+
+#[test]
+fn duplicates() {
+    let mut bundle = Bundle::new();
+    bundle
+        .add(&css(".a { color: red }"))
+        .add(&css(".b { color: blue }"))
+        .add(&css(".a { color: red }"));
+
+    assert_eq!(bundle.content(), ".a { color: red }\n.b { color: blue }\n");
+}
+
+#[test]
+fn hash_collisions() {
+    // "costarring" and "liquid" have the same 32 bit FNV-1a hash, so
+    // stylesheets mustn't be identified by their hash.
+    let first = "costarring";
+    let second = "liquid";
+    let mut bundle = Bundle::new();
+    bundle.add(&css(first)).add(&css(second));
+
+    assert_eq!(bundle.content(), format!("{first}\n{second}\n"));
+}
+
+#[test]
+fn scoped_stylesheets() {
+    let mut scoped = css(".a { color: red }");
+    let scope = scoped.scope_attribute();
+    scoped.scope(&scope);
+
+    let mut bundle = Bundle::new();
+    bundle.add(&scoped);
+
+    assert_eq!(bundle.content(), format!(".a[{scope}]{{ color: red }}\n"));
+}
+
+#[test]
+fn write() {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("bundle");
+    let mut bundle = Bundle::new();
+    bundle.add(&css(".a { color: red }"));
+
+    let path = bundle.write(&out_dir, "styles").unwrap();
+    let file_name = path.file_name().unwrap().to_str().unwrap();
+
+    assert!(file_name.starts_with("styles-"));
+    assert!(file_name.ends_with(".css"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), bundle.content());
+    // The name only depends on the content.
+    assert_eq!(bundle.write(&out_dir, "styles").unwrap(), path);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "css-transpile")]
+
+use silkenweb_css::{Css, NameMapping, Transpile};
+
+fn transpile(path: &str) -> (Css, Vec<NameMapping>) {
+    let mut css = Css::from_path(path, None).unwrap();
+    let names = css
+        .transpile(
+            false,
+            Some(Transpile {
+                minify: false,
+                pretty: false,
+                modules: true,
+                nesting: false,
+                browsers: None,
+            }),
+        )
+        .unwrap()
+        .unwrap();
+
+    (css, names)
+}
+
+fn class<'a>(names: &'a [NameMapping], plain: &str) -> &'a NameMapping {
+    names.iter().find(|name| name.plain == plain).unwrap()
+}
+
+#[test]
+fn composes_from_file() {
+    // Refer to each file by a different, but equivalent, path.
+    let (base, base_names) = transpile("tests/composes/../composes/base.css");
+    let (button, button_names) = transpile("tests/./composes/button.css");
+
+    let base_class = class(&base_names, "base");
+    let button_class = class(&button_names, "button");
+
+    assert_ne!(base_class.mangled, "base");
+    assert_eq!(button_class.composes, [base_class.mangled.clone()]);
+    assert!(button
+        .dependencies()
+        .any(|dependency| Some(dependency) == base.dependency()));
+}
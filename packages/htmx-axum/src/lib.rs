@@ -1,48 +1,406 @@
+use std::{convert::Infallible, fmt::Write};
+
 use async_trait::async_trait;
 use axum::{
-    body::{Bytes, HttpBody},
-    extract::FromRequest,
+    extract::FromRequestParts,
     headers::ContentType,
-    http::{self, Request},
+    http::{request::Parts, HeaderMap, HeaderName, HeaderValue},
     response::{IntoResponse, Response},
-    BoxError, TypedHeader,
+    TypedHeader,
+};
+pub use post::{HtmxPostRequest, HtmxRejection, HtmxUpload, UploadedFile};
+use serde::Serialize;
+pub use silkenweb::htmx::{Swap, SwapStyle};
+use silkenweb::{
+    document::{self, Document},
+    dom::Dry,
+    elements::html::div,
+    htmx::HtmxElement,
+    node::element::Element,
+    prelude::Node,
+    task,
 };
-use serde::de::DeserializeOwned;
-use silkenweb::{dom::Dry, prelude::Node};
 
-pub struct HtmxResponse(Node<Dry>);
+mod post;
+
+/// An htmx response.
+///
+/// The body is a primary fragment, which is swapped into the target, followed
+/// by any out of band fragments.
+///
+/// Stylesheets and other `<head>` entries mounted in the current
+/// [`task::scope`] are added to the document `<head>`. The server can't tell
+/// what the client already has, so any entries with the same
+/// `data-silkenweb-head-id` are removed first, rather than being duplicated.
+/// The entries are collected when the response is converted with
+/// [`IntoResponse::into_response`], so that must be called in the same
+/// [`task::scope`] that created the fragments:
+///
+/// ```
+/// # use axum::response::{IntoResponse, Response};
+/// # use silkenweb::{dom::Dry, elements::html::p, prelude::*, task};
+/// # use silkenweb_htmx_axum::HtmxResponse;
+/// async fn handler() -> Response {
+///     task::scope(async { HtmxResponse::new(p::<Dry>().text("Hello")).into_response() })
+///         .await
+/// }
+/// ```
+pub struct HtmxResponse {
+    node: Option<Node<Dry>>,
+    oob: Vec<Node<Dry>>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    triggers: Vec<(String, serde_json::Value)>,
+}
 
 impl HtmxResponse {
     pub fn new(node: impl Into<Node<Dry>>) -> Self {
-        Self(node.into())
+        Self {
+            node: Some(node.into()),
+            ..Self::empty()
+        }
+    }
+
+    /// A response with an empty body, for example to redirect.
+    pub fn empty() -> Self {
+        Self {
+            node: None,
+            oob: Vec::new(),
+            headers: Vec::new(),
+            triggers: Vec::new(),
+        }
+    }
+
+    /// Add an out of band fragment.
+    ///
+    /// `elem` is swapped into the element with the same `id` using `swap`,
+    /// rather than into the target. [`SwapStyle::OuterHtml`] replaces the
+    /// element, and the other styles swap in `elem`'s children.
+    pub fn oob<E>(mut self, swap: SwapStyle, elem: E) -> Self
+    where
+        E: HtmxElement + Into<Node<Dry>>,
+    {
+        self.oob.push(elem.hx_swap_oob(swap).into());
+        self
+    }
+
+    /// Set the `HX-Redirect` header, to redirect the client to `url`.
+    ///
+    /// Characters that aren't allowed in a header, such as non-ASCII
+    /// characters, are percent encoded.
+    pub fn redirect(self, url: impl AsRef<str>) -> Self {
+        self.header(HX_REDIRECT, &encode_url(url.as_ref()))
+    }
+
+    /// Set the `HX-Push-Url` header, to push `url` into the browser history.
+    ///
+    /// Characters that aren't allowed in a header, such as non-ASCII
+    /// characters, are percent encoded.
+    pub fn push_url(self, url: impl AsRef<str>) -> Self {
+        self.header(HX_PUSH_URL, &encode_url(url.as_ref()))
+    }
+
+    /// Add an event to the `HX-Trigger` header.
+    ///
+    /// Each event is only triggered once, so adding an event again replaces
+    /// its payload, if it has one.
+    pub fn trigger(self, event: impl Into<String>) -> Self {
+        self.add_trigger(event.into(), serde_json::Value::Null)
+    }
+
+    /// Add an event with a JSON payload to the `HX-Trigger` header.
+    ///
+    /// Each event is only triggered once, so adding an event again replaces
+    /// its payload.
+    ///
+    /// # Panics
+    ///
+    /// If `payload` can't be serialized to JSON.
+    pub fn trigger_with(self, event: impl Into<String>, payload: impl Serialize) -> Self {
+        let payload = serde_json::to_value(payload).expect("Couldn't serialize event payload");
+        self.add_trigger(event.into(), payload)
+    }
+
+    /// Set the `HX-Retarget` header, to swap the response into the elements
+    /// that match the CSS `selector`.
+    ///
+    /// Characters that aren't allowed in a header, such as non-ASCII
+    /// characters, are replaced with CSS escapes.
+    pub fn retarget(self, selector: impl AsRef<str>) -> Self {
+        self.header(HX_RETARGET, &escape_css(selector.as_ref()))
+    }
+
+    /// Set the `HX-Reswap` header, to change how the response is swapped in.
+    ///
+    /// Characters in selectors that aren't allowed in a header are replaced
+    /// with CSS escapes.
+    pub fn reswap(self, swap: impl Into<Swap>) -> Self {
+        self.header(HX_RESWAP, &escape_css(&swap.into().to_string()))
+    }
+
+    fn header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers
+            .push((HeaderName::from_static(name), header_value(value)));
+        self
+    }
+
+    fn add_trigger(mut self, event: String, payload: serde_json::Value) -> Self {
+        match self.triggers.iter_mut().find(|(name, _)| *name == event) {
+            Some((_, existing)) => *existing = payload,
+            None => self.triggers.push((event, payload)),
+        }
+
+        self
+    }
+
+    /// A comma separated list of events, if they're simple names without
+    /// payloads, or a JSON object mapping event names to payloads.
+    fn trigger_header(&self) -> Option<String> {
+        if self.triggers.is_empty() {
+            return None;
+        }
+
+        let is_simple = |name: &str| {
+            !name.is_empty() && name.bytes().all(|b| b.is_ascii_graphic() && b != b',')
+        };
+
+        if self
+            .triggers
+            .iter()
+            .all(|(name, payload)| payload.is_null() && is_simple(name))
+        {
+            let events: Vec<&str> = self
+                .triggers
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect();
+            return Some(events.join(", "));
+        }
+
+        let events: serde_json::Map<String, serde_json::Value> =
+            self.triggers.iter().cloned().collect();
+        Some(ascii_json(&serde_json::Value::Object(events)))
+    }
+
+    fn body(&self) -> Option<String> {
+        if self.node.is_none() && self.oob.is_empty() {
+            return None;
+        }
+
+        let mut body = String::new();
+
+        for node in self.node.iter().chain(&self.oob) {
+            body.push_str(&node.to_string());
+        }
+
+        if task::in_scope() {
+            let head = Dry::head_inner_html();
+
+            if !head.is_empty() {
+                // Remove existing copies of the entries, so they're replaced
+                // rather than duplicated.
+                for id in document::used_head_ids() {
+                    let remove = div::<Dry>().attribute(
+                        "hx-swap-oob",
+                        format!("delete:head > [data-silkenweb-head-id={}]", css_string(&id)),
+                    );
+                    body.push_str(&remove.freeze().to_string());
+                }
+
+                body.push_str(&format!(
+                    r#"<div hx-swap-oob="beforeend:head">{head}</div>"#
+                ));
+            }
+        }
+
+        Some(body)
+    }
+
+    fn header_map(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let trigger = self
+            .trigger_header()
+            .map(|trigger| (HeaderName::from_static(HX_TRIGGER), header_value(&trigger)));
+
+        for (name, value) in self.headers.iter().cloned().chain(trigger) {
+            headers.insert(name, value);
+        }
+
+        headers
     }
 }
 
 impl IntoResponse for HtmxResponse {
     fn into_response(self) -> Response {
-        (TypedHeader(ContentType::html()), self.0.to_string()).into_response()
+        let headers = self.header_map();
+
+        match self.body() {
+            Some(body) => (TypedHeader(ContentType::html()), headers, body).into_response(),
+            None => headers.into_response(),
+        }
+    }
+}
+
+macro_rules! flag_header {
+    ($(#[$meta:meta])* $name:ident = $header:expr) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+        pub struct $name(pub bool);
+
+        #[async_trait]
+        impl<State: Send + Sync> FromRequestParts<State> for $name {
+            type Rejection = Infallible;
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                _state: &State,
+            ) -> Result<Self, Self::Rejection> {
+                Ok(Self(header_str(parts, $header) == Some("true")))
+            }
+        }
+    };
+}
+
+macro_rules! string_header {
+    ($(#[$meta:meta])* $name:ident = $header:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub struct $name(pub Option<String>);
+
+        #[async_trait]
+        impl<State: Send + Sync> FromRequestParts<State> for $name {
+            type Rejection = Infallible;
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                _state: &State,
+            ) -> Result<Self, Self::Rejection> {
+                Ok(Self(header_str(parts, $header).map(str::to_string)))
+            }
+        }
+    };
+}
+
+flag_header!(
+    /// The `HX-Request` header, which is `true` for requests made by htmx.
+    HxRequest = HX_REQUEST
+);
+
+flag_header!(
+    /// The `HX-Boosted` header, which is `true` for requests from boosted
+    /// elements.
+    HxBoosted = HX_BOOSTED
+);
+
+string_header!(
+    /// The `HX-Target` header, with the `id` of the target element, if it has
+    /// one.
+    HxTarget = HX_TARGET
+);
+
+string_header!(
+    /// The `HX-Trigger` header, with the `id` of the triggered element, if it
+    /// has one.
+    HxTrigger = HX_TRIGGER
+);
+
+string_header!(
+    /// The `HX-Current-URL` header, with the current URL of the browser.
+    HxCurrentUrl = HX_CURRENT_URL
+);
+
+/// Quote `value` as a CSS string.
+fn css_string(value: &str) -> String {
+    let mut quoted = String::from('"');
+
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            c if c.is_control() => write!(quoted, "\\{:06x}", u32::from(c)).unwrap(),
+            c => quoted.push(c),
+        }
     }
+
+    quoted.push('"');
+    quoted
 }
 
-pub struct HtmxPostRequest<T>(pub T);
+/// Percent encode bytes in `url` that aren't visible ASCII characters.
+fn encode_url(url: &str) -> String {
+    let mut encoded = String::new();
+
+    for byte in url.bytes() {
+        if byte.is_ascii_graphic() {
+            encoded.push(char::from(byte));
+        } else {
+            write!(encoded, "%{byte:02X}").unwrap();
+        }
+    }
+
+    encoded
+}
 
-#[async_trait]
-impl<State, Body, T> FromRequest<State, Body> for HtmxPostRequest<T>
-where
-    State: Send + Sync,
-    Body: HttpBody + Send + 'static,
-    Body::Data: Send,
-    Body::Error: Into<BoxError>,
-    T: DeserializeOwned,
-{
-    type Rejection = http::StatusCode;
+/// Replace characters in `css` that aren't visible ASCII characters or spaces
+/// with CSS escapes.
+///
+/// The escapes always have 6 hex digits, so they don't need a terminating
+/// space.
+fn escape_css(css: &str) -> String {
+    let mut escaped = String::new();
 
-    async fn from_request(req: Request<Body>, state: &State) -> Result<Self, Self::Rejection> {
-        let bytes = Bytes::from_request(req, state)
-            .await
-            .map_err(|_| http::StatusCode::BAD_REQUEST)?;
-        serde_urlencoded::from_bytes(&bytes)
-            .map_err(|_| http::StatusCode::BAD_REQUEST)
-            .map(HtmxPostRequest)
+    for c in css.chars() {
+        if c.is_ascii_graphic() || c == ' ' {
+            escaped.push(c);
+        } else {
+            write!(escaped, "\\{:06x}", u32::from(c)).unwrap();
+        }
     }
+
+    escaped
 }
+
+/// Serialize `value` to JSON, escaping characters that aren't allowed in a
+/// header.
+///
+/// `serde_json` escapes control characters, but not non-ASCII characters or
+/// `DEL`. These can only appear in strings, so they're replaced with `\u`
+/// escapes.
+fn ascii_json(value: &serde_json::Value) -> String {
+    let mut json = String::new();
+
+    for c in value.to_string().chars() {
+        if c.is_ascii() && c != '\x7f' {
+            json.push(c);
+        } else {
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                write!(json, "\\u{unit:04x}").unwrap();
+            }
+        }
+    }
+
+    json
+}
+
+/// Convert an escaped or encoded string to a header value.
+fn header_value(value: &str) -> HeaderValue {
+    HeaderValue::from_str(value).expect("Header values should be escaped")
+}
+
+fn header_str<'a>(parts: &'a Parts, name: &str) -> Option<&'a str> {
+    parts
+        .headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+const HX_REQUEST: &str = "hx-request";
+const HX_BOOSTED: &str = "hx-boosted";
+const HX_TARGET: &str = "hx-target";
+const HX_TRIGGER: &str = "hx-trigger";
+const HX_CURRENT_URL: &str = "hx-current-url";
+const HX_REDIRECT: &str = "hx-redirect";
+const HX_PUSH_URL: &str = "hx-push-url";
+const HX_RETARGET: &str = "hx-retarget";
+const HX_RESWAP: &str = "hx-reswap";
@@ -0,0 +1,279 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use axum::{
+    body::{Bytes, HttpBody},
+    extract::{FromRequest, Multipart},
+    headers::ContentType,
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+    response::{IntoResponse, Response},
+    BoxError, TypedHeader,
+};
+use serde::de::DeserializeOwned;
+use silkenweb::{
+    dom::Dry,
+    elements::html::{div, li, ul},
+    prelude::{Element, Node, ParentElement},
+};
+
+/// Extract a form or JSON body from an htmx request.
+///
+/// `application/x-www-form-urlencoded`, `application/json` and
+/// `multipart/form-data` bodies are supported. Bodies without a `Content-Type`
+/// are treated as urlencoded. Multipart bodies can only have text fields, and
+/// empty file inputs, which have an empty file name and no data, are ignored.
+/// Use [`HtmxUpload`] to accept file uploads.
+pub struct HtmxPostRequest<T>(pub T);
+
+#[async_trait]
+impl<State, Body, T> FromRequest<State, Body> for HtmxPostRequest<T>
+where
+    State: Send + Sync,
+    Body: HttpBody<Data = Bytes> + Send + 'static,
+    Body::Error: Into<BoxError>,
+    T: DeserializeOwned,
+{
+    type Rejection = HtmxRejection;
+
+    async fn from_request(req: Request<Body>, state: &State) -> Result<Self, Self::Rejection> {
+        let (form, files) = read_body(req, state).await?;
+
+        if let Some(file) = files.first() {
+            return Err(HtmxRejection::UnexpectedFile(file.name.clone()));
+        }
+
+        Ok(Self(form))
+    }
+}
+
+/// Extract a form with file uploads from an htmx request.
+///
+/// This supports the same bodies as [`HtmxPostRequest`]. Fields from a
+/// `multipart/form-data` body with a file name are collected into `files`, and
+/// the other fields are deserialized into `form`. Empty file inputs aren't
+/// included in `files`.
+pub struct HtmxUpload<T> {
+    pub form: T,
+    pub files: Vec<UploadedFile>,
+}
+
+#[async_trait]
+impl<State, Body, T> FromRequest<State, Body> for HtmxUpload<T>
+where
+    State: Send + Sync,
+    Body: HttpBody<Data = Bytes> + Send + 'static,
+    Body::Error: Into<BoxError>,
+    T: DeserializeOwned,
+{
+    type Rejection = HtmxRejection;
+
+    async fn from_request(req: Request<Body>, state: &State) -> Result<Self, Self::Rejection> {
+        let (form, files) = read_body(req, state).await?;
+        Ok(Self { form, files })
+    }
+}
+
+/// A file from a `multipart/form-data` body.
+#[derive(Clone, Debug)]
+pub struct UploadedFile {
+    /// The name of the form field.
+    pub name: String,
+    /// The file name supplied by the client. Don't trust it as a path.
+    pub file_name: String,
+    /// The `Content-Type` of the file, if the client supplied one.
+    pub content_type: Option<String>,
+    /// The file's contents.
+    pub data: Bytes,
+}
+
+/// Why an htmx request body was rejected.
+///
+/// This renders as a fragment listing the [`messages`][Self::messages], with
+/// an error status. htmx doesn't swap in error responses by default, so either
+/// configure the client to, or extract a `Result<HtmxPostRequest<T>,
+/// HtmxRejection>` and return the [`fragment`][Self::fragment] in an
+/// [`HtmxResponse`][crate::HtmxResponse].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HtmxRejection {
+    /// The request had an unsupported `Content-Type`.
+    UnsupportedContentType(String),
+    /// The request body couldn't be read.
+    Body(String),
+    /// The request body couldn't be deserialized.
+    Deserialize(String),
+    /// [`HtmxPostRequest`] received a file in the named field.
+    UnexpectedFile(String),
+    /// The request failed validation by the handler.
+    Invalid(Vec<String>),
+}
+
+impl HtmxRejection {
+    /// A validation failure, with a message for each problem.
+    pub fn invalid(messages: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::Invalid(messages.into_iter().map(Into::into).collect())
+    }
+
+    /// The status code of the response.
+    ///
+    /// This is `415 Unsupported Media Type` for an unsupported `Content-Type`,
+    /// `400 Bad Request` if the body couldn't be read, and `422 Unprocessable
+    /// Entity` otherwise.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::UnsupportedContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Body(_) => StatusCode::BAD_REQUEST,
+            Self::Deserialize(_) | Self::UnexpectedFile(_) | Self::Invalid(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+        }
+    }
+
+    /// Messages describing the problem, suitable to show to the user.
+    pub fn messages(&self) -> Vec<String> {
+        match self {
+            Self::Invalid(messages) => messages.clone(),
+            _ => vec![self.to_string()],
+        }
+    }
+
+    /// A fragment listing the [`messages`][Self::messages].
+    pub fn fragment(&self) -> Node<Dry> {
+        div()
+            .class("htmx-rejection")
+            .child(ul().children(self.messages().into_iter().map(|msg| li().text(msg))))
+            .into()
+    }
+}
+
+impl fmt::Display for HtmxRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedContentType(content_type) => {
+                write!(f, "Unsupported content type: {content_type}")
+            }
+            Self::Body(e) => write!(f, "Couldn't read request: {e}"),
+            Self::Deserialize(e) => write!(f, "Invalid form data: {e}"),
+            Self::UnexpectedFile(name) => write!(f, "Unexpected file in field `{name}`"),
+            Self::Invalid(messages) => f.write_str(&messages.join("\n")),
+        }
+    }
+}
+
+impl std::error::Error for HtmxRejection {}
+
+impl IntoResponse for HtmxRejection {
+    fn into_response(self) -> Response {
+        (
+            self.status(),
+            TypedHeader(ContentType::html()),
+            self.fragment().to_string(),
+        )
+            .into_response()
+    }
+}
+
+async fn read_body<State, Body, T>(
+    req: Request<Body>,
+    state: &State,
+) -> Result<(T, Vec<UploadedFile>), HtmxRejection>
+where
+    State: Send + Sync,
+    Body: HttpBody<Data = Bytes> + Send + 'static,
+    Body::Error: Into<BoxError>,
+    T: DeserializeOwned,
+{
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+    let mime = content_type
+        .as_deref()
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase());
+
+    // Treat requests without a `Content-Type` as urlencoded, so clients that
+    // don't set one still work.
+    match mime.as_deref() {
+        None | Some("application/x-www-form-urlencoded") => {
+            let bytes = read_bytes(req, state).await?;
+            let form = serde_urlencoded::from_bytes(&bytes)
+                .map_err(|e| HtmxRejection::Deserialize(e.to_string()))?;
+            Ok((form, Vec::new()))
+        }
+        Some("application/json") => {
+            let bytes = read_bytes(req, state).await?;
+            let form = serde_json::from_slice(&bytes)
+                .map_err(|e| HtmxRejection::Deserialize(e.to_string()))?;
+            Ok((form, Vec::new()))
+        }
+        Some("multipart/form-data") => read_multipart(req, state).await,
+        Some(_) => Err(HtmxRejection::UnsupportedContentType(
+            content_type.unwrap_or_default(),
+        )),
+    }
+}
+
+async fn read_bytes<State, Body>(req: Request<Body>, state: &State) -> Result<Bytes, HtmxRejection>
+where
+    State: Send + Sync,
+    Body: HttpBody<Data = Bytes> + Send + 'static,
+    Body::Error: Into<BoxError>,
+{
+    Bytes::from_request(req, state)
+        .await
+        .map_err(|e| HtmxRejection::Body(e.body_text()))
+}
+
+async fn read_multipart<State, Body, T>(
+    req: Request<Body>,
+    state: &State,
+) -> Result<(T, Vec<UploadedFile>), HtmxRejection>
+where
+    State: Send + Sync,
+    Body: HttpBody<Data = Bytes> + Send + 'static,
+    Body::Error: Into<BoxError>,
+    T: DeserializeOwned,
+{
+    let mut multipart = Multipart::from_request(req, state)
+        .await
+        .map_err(|e| HtmxRejection::Body(e.body_text()))?;
+    let mut fields = Vec::new();
+    let mut files = Vec::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(body_error)? {
+        let name = field.name().unwrap_or_default().to_string();
+
+        if let Some(file_name) = field.file_name().map(str::to_string) {
+            let content_type = field.content_type().map(str::to_string);
+            let data = field.bytes().await.map_err(body_error)?;
+
+            // Browsers send an empty file input as a part with an empty file
+            // name and no data.
+            if file_name.is_empty() && data.is_empty() {
+                continue;
+            }
+
+            files.push(UploadedFile {
+                name,
+                file_name,
+                content_type,
+                data,
+            });
+        } else {
+            fields.push((name, field.text().await.map_err(body_error)?));
+        }
+    }
+
+    // Round trip the text fields through `serde_urlencoded`, so they're
+    // deserialized exactly like a urlencoded form.
+    let encoded = serde_urlencoded::to_string(&fields)
+        .map_err(|e| HtmxRejection::Deserialize(e.to_string()))?;
+    let form = serde_urlencoded::from_str(&encoded)
+        .map_err(|e| HtmxRejection::Deserialize(e.to_string()))?;
+
+    Ok((form, files))
+}
+
+fn body_error(e: impl fmt::Display) -> HtmxRejection {
+    HtmxRejection::Body(e.to_string())
+}
@@ -0,0 +1,120 @@
+use axum::{
+    body::HttpBody,
+    response::{IntoResponse, Response},
+};
+use silkenweb::{
+    document::{Document, DocumentHead},
+    dom::Dry,
+    elements::html::{div, meta, p},
+    prelude::*,
+    task::{self, render_now, server::block_on},
+};
+use silkenweb_htmx_axum::{HtmxResponse, SwapStyle};
+
+const STYLESHEET_HEAD: &str = concat!(
+    r#"<div hx-swap-oob="delete:head &gt; [data-silkenweb-head-id=&quot;sheet&quot;]"></div>"#,
+    r#"<div hx-swap-oob="beforeend:head">"#,
+    r#"<style data-silkenweb-head-id="sheet">.a {}</style>"#,
+    "</div>"
+);
+
+async fn body(response: Response) -> String {
+    let mut body = response.into_body();
+    let mut bytes = Vec::new();
+
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk.unwrap());
+    }
+
+    String::from_utf8(bytes).unwrap()
+}
+
+fn hello() -> HtmxResponse {
+    HtmxResponse::new(p::<Dry>().text("Hello"))
+}
+
+#[test]
+fn out_of_scope() {
+    block_on(async {
+        assert_eq!(body(hello().into_response()).await, "<p>Hello</p>");
+    })
+}
+
+#[test]
+fn oob_fragments() {
+    block_on(async {
+        let response = hello()
+            .oob(SwapStyle::OuterHtml, div().id("count").text("1"))
+            .oob(SwapStyle::BeforeEnd, div().id("log").text("Said hello"));
+
+        assert_eq!(
+            body(response.into_response()).await,
+            concat!(
+                "<p>Hello</p>",
+                r#"<div id="count" hx-swap-oob="outerHTML">1</div>"#,
+                r#"<div id="log" hx-swap-oob="beforeend">Said hello</div>"#
+            )
+        );
+    })
+}
+
+#[test]
+fn head_entries_replace_existing() {
+    let response = task::sync_scope(|| {
+        Dry::mount_stylesheet("sheet", ".a {}");
+        hello().into_response()
+    });
+
+    assert_eq!(
+        block_on(body(response)),
+        format!("<p>Hello</p>{STYLESHEET_HEAD}")
+    );
+}
+
+#[test]
+fn head_collected_on_conversion() {
+    let response = task::sync_scope(|| {
+        let response = hello();
+        Dry::mount_stylesheet("sheet", ".a {}");
+        response.into_response()
+    });
+
+    assert_eq!(
+        block_on(body(response)),
+        format!("<p>Hello</p>{STYLESHEET_HEAD}")
+    );
+}
+
+#[test]
+fn head_ids_are_quoted() {
+    let response = block_on(task::scope(async {
+        Dry::mount_in_head(
+            r#"my"id"#,
+            DocumentHead::new().child(meta().name("description")),
+        );
+        render_now().await;
+        hello().into_response()
+    }));
+
+    assert_eq!(
+        block_on(body(response)),
+        concat!(
+            "<p>Hello</p>",
+            r#"<div hx-swap-oob="delete:head &gt; [data-silkenweb-head-id=&quot;my\&quot;id&quot;]"></div>"#,
+            r#"<div hx-swap-oob="beforeend:head">"#,
+            r#"<meta name="description" data-silkenweb-head-id="my&quot;id">"#,
+            "</div>"
+        )
+    );
+}
+
+#[test]
+fn empty_response_has_no_head() {
+    let response = task::sync_scope(|| {
+        Dry::mount_stylesheet("sheet", ".a {}");
+        HtmxResponse::empty().into_response()
+    });
+
+    assert!(response.headers().get("content-type").is_none());
+    assert_eq!(block_on(body(response)), "");
+}
@@ -0,0 +1,138 @@
+use std::fmt::Debug;
+
+use axum::{
+    extract::FromRequestParts,
+    http::Request,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use silkenweb::htmx::ScrollPosition;
+use silkenweb_htmx_axum::{
+    HtmxResponse, HxBoosted, HxCurrentUrl, HxRequest, HxTarget, HxTrigger, Swap, SwapStyle,
+};
+
+async fn extract<T>(headers: &[(&str, &str)]) -> T
+where
+    T: FromRequestParts<()>,
+    T::Rejection: Debug,
+{
+    let mut request = Request::builder();
+
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let (mut parts, ()) = request.body(()).unwrap().into_parts();
+    T::from_request_parts(&mut parts, &()).await.unwrap()
+}
+
+fn header(response: &Response, name: &str) -> String {
+    response.headers()[name].to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn flag_headers() {
+    assert_eq!(
+        extract::<HxRequest>(&[("HX-Request", "true")]).await,
+        HxRequest(true)
+    );
+    assert_eq!(
+        extract::<HxRequest>(&[("HX-Request", "false")]).await,
+        HxRequest(false)
+    );
+    assert_eq!(extract::<HxRequest>(&[]).await, HxRequest(false));
+
+    assert_eq!(
+        extract::<HxBoosted>(&[("HX-Boosted", "true")]).await,
+        HxBoosted(true)
+    );
+    assert_eq!(extract::<HxBoosted>(&[]).await, HxBoosted(false));
+}
+
+#[tokio::test]
+async fn string_headers() {
+    assert_eq!(
+        extract::<HxTarget>(&[("HX-Target", "todo-list")]).await,
+        HxTarget(Some("todo-list".to_string()))
+    );
+    assert_eq!(extract::<HxTarget>(&[]).await, HxTarget(None));
+
+    assert_eq!(
+        extract::<HxTrigger>(&[("HX-Trigger", "add-button")]).await,
+        HxTrigger(Some("add-button".to_string()))
+    );
+    assert_eq!(extract::<HxTrigger>(&[]).await, HxTrigger(None));
+
+    assert_eq!(
+        extract::<HxCurrentUrl>(&[("HX-Current-URL", "https://example.com/todo")]).await,
+        HxCurrentUrl(Some("https://example.com/todo".to_string()))
+    );
+    assert_eq!(extract::<HxCurrentUrl>(&[]).await, HxCurrentUrl(None));
+}
+
+#[test]
+fn trigger_list() {
+    let response = HtmxResponse::empty()
+        .trigger("added")
+        .trigger("updated")
+        .into_response();
+
+    assert_eq!(header(&response, "HX-Trigger"), "added, updated");
+}
+
+#[test]
+fn trigger_json() {
+    let response = HtmxResponse::empty()
+        .trigger("added")
+        .trigger_with("updated", json!({ "id": 1 }))
+        .into_response();
+
+    assert_eq!(
+        header(&response, "HX-Trigger"),
+        r#"{"added":null,"updated":{"id":1}}"#
+    );
+
+    // Names that can't be in a comma separated list use JSON.
+    let response = HtmxResponse::empty().trigger("my event").into_response();
+
+    assert_eq!(header(&response, "HX-Trigger"), r#"{"my event":null}"#);
+}
+
+#[test]
+fn duplicate_triggers() {
+    let response = HtmxResponse::empty()
+        .trigger("added")
+        .trigger("updated")
+        .trigger("added")
+        .into_response();
+
+    assert_eq!(header(&response, "HX-Trigger"), "added, updated");
+
+    let response = HtmxResponse::empty()
+        .trigger_with("updated", 1)
+        .trigger_with("updated", 2)
+        .into_response();
+
+    assert_eq!(header(&response, "HX-Trigger"), r#"{"updated":2}"#);
+}
+
+#[test]
+fn non_ascii_headers() {
+    let response = HtmxResponse::empty()
+        .redirect("/café?q=a b")
+        .push_url("/ü")
+        .retarget("#café")
+        .reswap(Swap::new(SwapStyle::InnerHtml).show(ScrollPosition::Top, Some("#ü")))
+        .trigger_with("é", "ü")
+        .into_response();
+
+    assert!(response.status().is_success());
+    assert_eq!(header(&response, "HX-Redirect"), "/caf%C3%A9?q=a%20b");
+    assert_eq!(header(&response, "HX-Push-Url"), "/%C3%BC");
+    assert_eq!(header(&response, "HX-Retarget"), r"#caf\0000e9");
+    assert_eq!(
+        header(&response, "HX-Reswap"),
+        r"innerHTML show:#\0000fc:top"
+    );
+    assert_eq!(header(&response, "HX-Trigger"), r#"{"\u00e9":"\u00fc"}"#);
+}
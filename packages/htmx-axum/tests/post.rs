@@ -0,0 +1,234 @@
+use axum::{
+    body::Body,
+    extract::FromRequest,
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use silkenweb_htmx_axum::{HtmxPostRequest, HtmxRejection, HtmxUpload};
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Name {
+    first: String,
+    last: String,
+}
+
+fn ada() -> Name {
+    Name {
+        first: "Ada".to_string(),
+        last: "Lovelace".to_string(),
+    }
+}
+
+const BOUNDARY: &str = "BOUNDARY";
+
+fn request(content_type: Option<&str>, body: impl Into<Body>) -> Request<Body> {
+    let mut request = Request::builder().method("POST");
+
+    if let Some(content_type) = content_type {
+        request = request.header(CONTENT_TYPE, content_type);
+    }
+
+    request.body(body.into()).unwrap()
+}
+
+/// A `multipart/form-data` request with `(name, file_name, data)` parts.
+fn multipart(parts: &[(&str, Option<&str>, &str)]) -> Request<Body> {
+    let mut body = String::new();
+
+    for (name, file_name, data) in parts {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\""
+        ));
+
+        if let Some(file_name) = file_name {
+            body.push_str(&format!(
+                "; filename=\"{file_name}\"\r\nContent-Type: application/octet-stream"
+            ));
+        }
+
+        body.push_str(&format!("\r\n\r\n{data}\r\n"));
+    }
+
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+
+    request(
+        Some(&format!("multipart/form-data; boundary={BOUNDARY}")),
+        body,
+    )
+}
+
+async fn post(request: Request<Body>) -> Result<Name, HtmxRejection> {
+    let HtmxPostRequest(name) = HtmxPostRequest::from_request(request, &()).await?;
+    Ok(name)
+}
+
+async fn upload(request: Request<Body>) -> Result<HtmxUpload<Name>, HtmxRejection> {
+    HtmxUpload::from_request(request, &()).await
+}
+
+#[tokio::test]
+async fn urlencoded() {
+    let body = "first=Ada&last=Lovelace";
+
+    assert_eq!(
+        post(request(Some("application/x-www-form-urlencoded"), body)).await,
+        Ok(ada())
+    );
+    assert_eq!(
+        post(request(
+            Some("Application/X-WWW-Form-Urlencoded; charset=UTF-8"),
+            body
+        ))
+        .await,
+        Ok(ada())
+    );
+}
+
+#[tokio::test]
+async fn missing_content_type() {
+    assert_eq!(
+        post(request(None, "first=Ada&last=Lovelace")).await,
+        Ok(ada())
+    );
+}
+
+#[tokio::test]
+async fn json() {
+    assert_eq!(
+        post(request(
+            Some("application/json"),
+            r#"{"first": "Ada", "last": "Lovelace"}"#
+        ))
+        .await,
+        Ok(ada())
+    );
+}
+
+#[tokio::test]
+async fn multipart_fields() {
+    let request = multipart(&[("first", None, "Ada"), ("last", None, "Lovelace")]);
+    assert_eq!(post(request).await, Ok(ada()));
+}
+
+#[tokio::test]
+async fn empty_file_inputs() {
+    let parts = [
+        ("first", None, "Ada"),
+        ("avatar", Some(""), ""),
+        ("last", None, "Lovelace"),
+    ];
+
+    assert_eq!(post(multipart(&parts)).await, Ok(ada()));
+
+    let upload = upload(multipart(&parts)).await.unwrap();
+    assert_eq!(upload.form, ada());
+    assert!(upload.files.is_empty());
+}
+
+#[tokio::test]
+async fn uploads() {
+    let upload = upload(multipart(&[
+        ("first", None, "Ada"),
+        ("avatar", Some("ada.png"), "PNG"),
+        ("last", None, "Lovelace"),
+    ]))
+    .await
+    .unwrap();
+
+    assert_eq!(upload.form, ada());
+    assert_eq!(upload.files.len(), 1);
+
+    let file = &upload.files[0];
+    assert_eq!(file.name, "avatar");
+    assert_eq!(file.file_name, "ada.png");
+    assert_eq!(
+        file.content_type.as_deref(),
+        Some("application/octet-stream")
+    );
+    assert_eq!(file.data.as_ref(), b"PNG");
+}
+
+#[tokio::test]
+async fn unsupported_content_type() {
+    let rejection = post(request(Some("text/plain"), "Ada Lovelace"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        rejection,
+        HtmxRejection::UnsupportedContentType("text/plain".to_string())
+    );
+    assert_eq!(rejection.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn unreadable_body() {
+    // A multipart body needs a boundary.
+    let rejection = post(request(Some("multipart/form-data"), ""))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(rejection, HtmxRejection::Body(_)));
+    assert_eq!(rejection.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn invalid_form_data() {
+    let rejection = post(request(
+        Some("application/x-www-form-urlencoded"),
+        "first=Ada",
+    ))
+    .await
+    .unwrap_err();
+
+    assert!(matches!(rejection, HtmxRejection::Deserialize(_)));
+    assert_eq!(rejection.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let rejection = post(request(Some("application/json"), "{"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(rejection, HtmxRejection::Deserialize(_)));
+}
+
+#[tokio::test]
+async fn unexpected_file() {
+    let rejection = post(multipart(&[
+        ("first", None, "Ada"),
+        ("last", None, "Lovelace"),
+        ("avatar", Some("ada.png"), "PNG"),
+    ]))
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        rejection,
+        HtmxRejection::UnexpectedFile("avatar".to_string())
+    );
+    assert_eq!(rejection.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[test]
+fn invalid() {
+    let rejection = HtmxRejection::invalid(["First name is required", "Last name is too long"]);
+
+    assert_eq!(rejection.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        rejection.messages(),
+        ["First name is required", "Last name is too long"]
+    );
+    assert_eq!(
+        rejection.fragment().to_string(),
+        concat!(
+            r#"<div class="htmx-rejection"><ul>"#,
+            "<li>First name is required</li>",
+            "<li>Last name is too long</li>",
+            "</ul></div>"
+        )
+    );
+    assert_eq!(
+        rejection.into_response().status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
@@ -2,23 +2,33 @@
 //!
 //! See [`silkenweb_parse`] for details on the parsing.
 use std::{
-    env, fs,
+    env, fmt, fs,
     path::{Path, PathBuf},
 };
 
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
-use proc_macro_error::{abort_call_site, proc_macro_error};
-use quote::quote;
-use silkenweb_parse::html_to_tokens;
-use syn::{parse_macro_input, LitStr};
+use proc_macro_error::{abort, abort_call_site, proc_macro_error};
+use quote::{format_ident, quote};
+use silkenweb_parse::{html_to_elements, ElementTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, LitStr, Token,
+};
 
 /// Include an HTML snippet from a string literal.
 ///
-/// Take a string literal containing a single HTML element, and produce a
-/// [`Node`][silkenweb::node::Node] expression. The [`Dom`][silkenweb::dom::Dom]
-/// type is not specified, so if it can't be determined by type inference, you
-/// may need to provide a type annotation.
+/// Take a string literal containing a single HTML element, and produce an
+/// element expression. Known HTML and SVG elements are built with their typed
+/// builders, such as [`Div`][silkenweb::elements::html::Div], so events can be
+/// added to the root element. The [`Dom`][silkenweb::dom::Dom] type is not
+/// specified, so if it can't be determined by type inference, you may need to
+/// provide a type annotation.
+///
+/// With the `with_ids` flag, the expression is a tuple of the element and a
+/// struct with an [`ElementHandle`] field for each element with an `id`
+/// attribute. The field names are derived from the `id` by replacing non
+/// alphanumeric characters with an `_`.
 ///
 /// See [`silkenweb_parse`] for details on the parsing.
 ///
@@ -26,57 +36,145 @@ use syn::{parse_macro_input, LitStr};
 ///
 /// ```
 /// # use silkenweb_inline_html::inline_html;
-/// # use silkenweb::node::Node;
-/// let node: Node = inline_html!("<p>Inline HTML</p>");
-/// assert_eq!(node.to_string(), "<p>Inline HTML</p>");
+/// # use silkenweb::elements::html::P;
+/// let elem: P = inline_html!("<p>Inline HTML</p>");
+/// assert_eq!(elem.freeze().to_string(), "<p>Inline HTML</p>");
+/// ```
+///
+/// With `id`s:
+///
+/// ```
+/// # use silkenweb_inline_html::inline_html;
+/// # use silkenweb::{elements::html::Div, node::element::ElementHandle};
+/// let (elem, ids): (Div, _) = inline_html!(
+///     r#"<div><button id="submit-button">Submit</button></div>"#,
+///     with_ids
+/// );
+/// let submit_button: ElementHandle<_, _> = ids.submit_button;
 /// ```
+///
+/// [`ElementHandle`]: silkenweb::node::element::ElementHandle
 #[proc_macro]
 #[proc_macro_error]
 pub fn inline_html(input: TokenStream) -> TokenStream {
-    let html: LitStr = parse_macro_input!(input);
-    let html_text = html.value();
-    let mut element_iter = html_to_tokens(quote! {D}.into(), &html_text).into_iter();
-    let element: proc_macro2::TokenStream = element_iter
-        .next()
-        .unwrap_or_else(|| abort_call_site!("Unable to parse any elements"))
-        .into();
+    let MacroInput {
+        literal: html,
+        flag,
+    } = parse_macro_input!(input);
+    let with_ids = check_flag(flag, "with_ids");
+    let element = single_element(&html.value()).unwrap_or_else(|e| abort_call_site!("{}", e));
 
-    if element_iter.next().is_some() {
-        abort_call_site!("Multiple elements found");
+    if with_ids {
+        let ids = ids_struct(&format_ident!("Ids"), &element);
+        let ElementTokens { element_type, .. } = &element;
+        let element_with_ids = element_with_ids(&format_ident!("Ids"), &element);
+
+        quote! {{
+            #ids
+
+            pub fn element<D: ::silkenweb::dom::Dom>() -> (#element_type, Ids<D>) {
+                #element_with_ids
+            }
+
+            element()
+        }}
+    } else {
+        let ElementTokens {
+            element_type,
+            element,
+            ..
+        } = element;
+
+        quote! {{
+            pub fn element<D: ::silkenweb::dom::Dom>() -> #element_type {
+                #element
+            }
+
+            element()
+        }}
     }
+    .into()
+}
+
+struct MacroInput {
+    literal: LitStr,
+    flag: Option<Ident>,
+}
 
-    quote! {{
-        pub fn node<D: ::silkenweb::dom::Dom>() -> ::silkenweb::node::Node<D> {
-            #element
+impl Parse for MacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let literal = input.parse()?;
+        let mut flag = None;
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+
+            if !input.is_empty() {
+                flag = Some(input.parse()?);
+                input.parse::<Option<Token![,]>>()?;
+            }
         }
 
-        node()
-    }}
-    .into()
+        Ok(Self { literal, flag })
+    }
+}
+
+/// Check that `flag` is `expected`, if it's specified.
+fn check_flag(flag: Option<Ident>, expected: &str) -> bool {
+    match flag {
+        Some(flag) if flag == expected => true,
+        Some(flag) => abort!(flag, "Expected `{}`", expected),
+        None => false,
+    }
 }
 
 /// Include an HTML snippet from a file.
 ///
 /// This takes a string literal as a filename, parses the contents of the file
-/// and puts the resulting expression into a funcion. The function name is
-/// derived from the filename by replacing non alphanumeric characters with an
-/// `_`.
+/// and puts the resulting element expression into a funcion. The function name
+/// is derived from the filename by replacing non alphanumeric characters with
+/// an `_`.
+///
+/// If any elements have an `id` attribute, a `{name}_with_ids` function is also
+/// generated. It returns a tuple of the element and a `{Name}Ids` struct, with
+/// an [`ElementHandle`] field for each element with an `id`. See
+/// [`inline_html!`] for details.
+///
+/// # Hot Reload
+///
+/// With the `hot_reload` flag, for example `html_file!("page.html",
+/// hot_reload)`, debug builds read the file at runtime under
+/// [`Dry`][silkenweb::dom::Dry], so template edits don't need a recompile
+/// when server side rendering. The function returns a
+/// [`Node`][silkenweb::node::Node], as the root element can change. The crate
+/// must depend on [`silkenweb_parse`] to use `hot_reload`. If the file can't be
+/// read or doesn't contain a single element, the compiled in version is used.
+/// `{name}_with_ids` functions always use the compiled in version.
 ///
 /// See `examples/inline-html` for a usage example.
 ///
 /// See [`silkenweb_parse`] for details on the parsing.
+///
+/// [`ElementHandle`]: silkenweb::node::element::ElementHandle
 #[proc_macro]
 #[proc_macro_error]
 pub fn html_file(input: TokenStream) -> TokenStream {
-    let file: LitStr = parse_macro_input!(input);
+    let MacroInput {
+        literal: file,
+        flag,
+    } = parse_macro_input!(input);
+    let hot_reload = check_flag(flag, "hot_reload");
     let file_path = root_dir().join(file.value());
-    html_from_path(&file_path).into()
+    html_from_path(&file_path, hot_reload).into()
 }
 
 /// Include HTML snippets from a directory of files.
 ///
 /// This takes a string literal as a directory name and is equivalent to running
-/// [`html_file!`] on every file directly contained in directory.
+/// [`html_file!`] on every file in the directory. Subdirectories become
+/// nested modules, with names derived in the same way as function names.
+///
+/// The `hot_reload` flag is passed on to [`html_file!`].
 ///
 /// See `examples/inline-html` for a usage example.
 ///
@@ -84,55 +182,189 @@ pub fn html_file(input: TokenStream) -> TokenStream {
 #[proc_macro]
 #[proc_macro_error]
 pub fn html_dir(input: TokenStream) -> TokenStream {
-    let dir_literal: LitStr = parse_macro_input!(input);
-    let dir = dir_literal.value();
-    let fns = fs::read_dir(root_dir().join(&dir))
-        .unwrap_or_else(|_| abort_call_site!("Unable to read dir '{}'", dir))
-        .filter_map(|entry| {
-            let path = entry
+    let MacroInput { literal: dir, flag } = parse_macro_input!(input);
+    let hot_reload = check_flag(flag, "hot_reload");
+
+    html_from_dir(&root_dir().join(dir.value()), hot_reload).into()
+}
+
+fn html_from_dir(dir: &Path, hot_reload: bool) -> proc_macro2::TokenStream {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|_| abort_call_site!("Unable to read dir '{:?}'", dir))
+        .map(|entry| {
+            entry
                 .unwrap_or_else(|_| abort_call_site!("Unable to read dir entry"))
-                .path();
+                .path()
+        })
+        .collect();
+    paths.sort();
+
+    let items = paths.iter().map(|path| {
+        if path.is_dir() {
+            let mod_name = name_to_ident(
+                path.file_name()
+                    .unwrap_or_else(|| abort_call_site!("Unable to extract name from '{:?}'", path))
+                    .to_str()
+                    .unwrap_or_else(|| {
+                        abort_call_site!("Directory name '{:?}' is not valid unicode", path)
+                    }),
+            );
+            let items = html_from_dir(path, hot_reload);
 
-            if path.is_file() {
-                Some(html_from_path(&path))
-            } else {
-                None
+            quote! {
+                pub mod #mod_name {
+                    #items
+                }
             }
-        });
+        } else {
+            html_from_path(path, hot_reload)
+        }
+    });
 
-    quote!(#(#fns)*).into()
+    quote!(#(#items)*)
 }
 
-fn html_from_path(file_path: &Path) -> proc_macro2::TokenStream {
+fn html_from_path(file_path: &Path, hot_reload: bool) -> proc_macro2::TokenStream {
     let html_text = fs::read_to_string(file_path)
         .unwrap_or_else(|_| abort_call_site!("Unable to read file '{:?}'", &file_path));
-    let mut element_iter = html_to_tokens(quote! {D}.into(), &html_text).into_iter();
-    let element: proc_macro2::TokenStream = element_iter
-        .next()
-        .unwrap_or_else(|| abort_call_site!("Unable to parse any elements for '{:?}'", &file_path))
-        .into();
-
-    if element_iter.next().is_some() {
-        abort_call_site!("Multiple elements found in '{:?}'", &file_path);
-    }
+    let element = single_element(&html_text)
+        .unwrap_or_else(|e| abort_call_site!("{} for '{:?}'", e, &file_path));
 
-    let fn_name = filename_to_ident(
+    let fn_name = name_to_ident(
         file_path
             .file_stem()
             .unwrap_or_else(|| {
                 abort_call_site!("Unable to extract file stem from '{:?}'", file_path)
             })
             .to_str()
-            .unwrap(),
+            .unwrap_or_else(|| {
+                abort_call_site!("File name '{:?}' is not valid unicode", file_path)
+            }),
     );
+    let ElementTokens {
+        element_type,
+        element: element_expr,
+        ids,
+        ..
+    } = &element;
+
+    let with_ids = if ids.is_empty() {
+        quote!()
+    } else {
+        let struct_name = format_ident!("{}Ids", upper_camel_case(&fn_name.to_string()));
+        let fn_with_ids = format_ident!("{fn_name}_with_ids");
+        let ids = ids_struct(&struct_name, &element);
+        let element_with_ids = element_with_ids(&struct_name, &element);
+
+        quote! {
+            #ids
+
+            pub fn #fn_with_ids<D: ::silkenweb::dom::Dom>() -> (#element_type, #struct_name<D>) {
+                #element_with_ids
+            }
+        }
+    };
+
+    let element_fn = if hot_reload {
+        let file_path = file_path
+            .to_str()
+            .unwrap_or_else(|| abort_call_site!("Path '{:?}' is not valid unicode", file_path));
+
+        quote! {
+            pub fn #fn_name<D: ::silkenweb::dom::Dom>() -> ::silkenweb::node::Node<D> {
+                #[cfg(debug_assertions)]
+                if ::std::any::TypeId::of::<D>() == ::std::any::TypeId::of::<::silkenweb::dom::Dry>() {
+                    if let ::std::option::Option::Some(node) =
+                        ::silkenweb_parse::hot_reload::read_node::<D>(#file_path)
+                    {
+                        return node;
+                    }
+                }
+
+                ::silkenweb::node::Node::from(#element_expr)
+            }
+        }
+    } else {
+        quote! {
+            pub fn #fn_name<D: ::silkenweb::dom::Dom>() -> #element_type {
+                #element_expr
+            }
+        }
+    };
 
     quote! {
-        pub fn #fn_name<D: ::silkenweb::dom::Dom>() -> ::silkenweb::node::Node<D> {
-            #element
+        #element_fn
+
+        #with_ids
+    }
+}
+
+enum ElementError {
+    None,
+    Multiple,
+}
+
+impl fmt::Display for ElementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("Unable to parse any elements"),
+            Self::Multiple => f.write_str("Multiple elements found"),
         }
     }
 }
 
+fn single_element(html: &str) -> Result<ElementTokens, ElementError> {
+    let mut element_iter = html_to_elements(quote! {D}.into(), html).into_iter();
+    let element = element_iter.next().ok_or(ElementError::None)?;
+
+    if element_iter.next().is_some() {
+        return Err(ElementError::Multiple);
+    }
+
+    Ok(element)
+}
+
+fn id_fields(element: &ElementTokens) -> Vec<Ident> {
+    let mut fields: Vec<Ident> = Vec::new();
+
+    for (id, _) in &element.ids {
+        let field = name_to_ident(id);
+
+        if fields.contains(&field) {
+            abort_call_site!("Duplicate id '{}'", id);
+        }
+
+        fields.push(field);
+    }
+
+    fields
+}
+
+fn ids_struct(name: &Ident, element: &ElementTokens) -> proc_macro2::TokenStream {
+    if element.ids.is_empty() {
+        abort_call_site!("No elements with an `id` attribute");
+    }
+
+    let fields = id_fields(element);
+    let types = element.ids.iter().map(|(_, ty)| ty);
+
+    quote! {
+        pub struct #name<D: ::silkenweb::dom::Dom> {
+            #(pub #fields: #types,)*
+        }
+    }
+}
+
+fn element_with_ids(name: &Ident, element: &ElementTokens) -> proc_macro2::TokenStream {
+    let fields = id_fields(element);
+    let element_with_ids = &element.element_with_ids;
+
+    quote! {
+        let (element, (#(#fields,)*)) = #element_with_ids;
+        (element, #name { #(#fields),* })
+    }
+}
+
 fn root_dir() -> PathBuf {
     const CARGO_MANIFEST_DIR: &str = "CARGO_MANIFEST_DIR";
 
@@ -142,7 +374,7 @@ fn root_dir() -> PathBuf {
     )
 }
 
-fn filename_to_ident(file: &str) -> Ident {
+fn name_to_ident(file: &str) -> Ident {
     let ident = file.replace(|c: char| !c.is_alphanumeric(), "_");
 
     if let Some(first) = ident.chars().next() {
@@ -153,5 +385,22 @@ fn filename_to_ident(file: &str) -> Ident {
         abort_call_site!("Empty identifier");
     }
 
+    if syn::parse_str::<Ident>(&ident).is_err() {
+        abort_call_site!("'{}' is not a valid identifier", ident);
+    }
+
     Ident::new(&ident, Span::call_site())
 }
+
+fn upper_camel_case(ident: &str) -> String {
+    ident
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().collect::<String>() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
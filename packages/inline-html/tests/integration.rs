@@ -1,13 +1,22 @@
-use silkenweb::{dom::Wet, elements::html::div, node::Node, prelude::ParentElement};
+use silkenweb::{
+    dom::Wet,
+    elements::{
+        html::{div, Div},
+        svg::Svg,
+        ElementEvents,
+    },
+    node::Node,
+    prelude::ParentElement,
+};
 use silkenweb_inline_html::{html_dir, html_file, inline_html};
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
-/// Check we can create a `Node` where `Dom` != `DefaultDom`.
+/// Check we can create an element where `Dom` != `DefaultDom`.
 #[test]
 fn inline_compile() {
-    let _ = || -> Node<Wet> { inline_html!(r#"<svg></svg>"#) };
+    let _ = || -> Node<Wet> { inline_html!(r#"<svg></svg>"#).into() };
 }
 
 #[wasm_bindgen_test]
@@ -30,10 +39,94 @@ fn file() {
 fn dir() {
     html_dir!("tests/svg");
     inline_html_test(image());
+    inline_html_test(nested::nested_image());
 }
 
-fn inline_html_test(image: Node) {
+/// The checked in page is compiled in, so the hot reloaded function is pointed
+/// at a copy of it to edit.
+#[cfg(debug_assertions)]
+#[test]
+fn hot_reload() {
+    use std::{fs, path::Path};
+
+    use silkenweb::dom::Dry;
+    use silkenweb_parse::hot_reload::with_path_override;
+
+    const COMPILED: &str = r#"<div class="page"><p>Page</p></div>"#;
+
+    html_file!("tests/hot-reload/page.html", hot_reload);
+
+    let page_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/hot-reload/page.html");
+    let copy_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("hot-reload");
+    let copy_path = copy_dir.join("page.html");
+    fs::create_dir_all(&copy_dir).unwrap();
+    fs::copy(&page_path, &copy_path).unwrap();
+
+    with_path_override(page_path.to_str().unwrap(), &copy_path, || {
+        assert_eq!(page::<Dry>().to_string(), COMPILED);
+
+        fs::write(&copy_path, r#"<p class="modified">Modified</p>"#).unwrap();
+        assert_eq!(
+            page::<Dry>().to_string(),
+            r#"<p class="modified">Modified</p>"#
+        );
+
+        // Files with more than one element fall back to the compiled version.
+        fs::write(&copy_path, "<p>First</p><p>Second</p>").unwrap();
+        assert_eq!(page::<Dry>().to_string(), COMPILED);
+
+        fs::remove_file(&copy_path).unwrap();
+        assert_eq!(page::<Dry>().to_string(), COMPILED);
+    });
+
+    // Without the override, the checked in page is read.
+    assert_eq!(page::<Dry>().to_string(), COMPILED);
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn typed_element() {
+    let elem: Div = inline_html!(r#"<div class="container"><p>Text</p></div>"#);
+    let elem = elem.on_click(|_, _| ()).freeze();
+
+    assert_eq!(
+        elem.to_string(),
+        r#"<div class="container"><p>Text</p></div>"#
+    );
+}
+
+/// Attributes with typed methods should render exactly as they were written.
+#[wasm_bindgen_test]
+#[test]
+fn typed_attributes() {
+    let elem: Div = inline_html!(
+        r#"<div><ol reversed="" start="3"><li>Item</li></ol><ol reversed="reversed" start="03"></ol><progress max="1.5" value="0.50"></progress><details open=""><summary>Summary</summary></details></div>"#
+    );
+
+    assert_eq!(
+        elem.freeze().to_string(),
+        r#"<div><ol reversed="" start="3"><li>Item</li></ol><ol reversed="reversed" start="03"></ol><progress max="1.5" value="0.50"></progress><details open=""><summary>Summary</summary></details></div>"#
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn with_ids() {
+    let (elem, ids): (Div, _) = inline_html!(
+        r#"<div id="container"><button id="submit-button">Submit</button></div>"#,
+        with_ids
+    );
+    let _container = ids.container;
+    let _submit_button = ids.submit_button;
+
+    assert_eq!(
+        elem.freeze().to_string(),
+        r#"<div id="container"><button id="submit-button">Submit</button></div>"#
+    );
+}
+
+fn inline_html_test(image: Svg) {
     let image_contents = include_str!("svg/image.svg");
     let expected = format!(r#"<div>{image_contents}</div>"#);
-    assert_eq!(div().children([image]).freeze().to_string(), expected);
+    assert_eq!(div().child(image).freeze().to_string(), expected);
 }
@@ -0,0 +1,282 @@
+//! An HTML-like template syntax that expands to the typed element builders.
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    braced,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Expr, Ident, Lit, LitStr, Token,
+};
+
+pub struct Input(Element);
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let element = input.parse()?;
+
+        if !input.is_empty() {
+            return Err(input.error("Expected a single root element"));
+        }
+
+        Ok(Self(element))
+    }
+}
+
+struct Element {
+    name: HtmlName,
+    attributes: Vec<Attribute>,
+    children: Vec<Child>,
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name: HtmlName = input.parse()?;
+        let mut attributes = Vec::new();
+
+        while !(input.peek(Token![/]) || input.peek(Token![>])) {
+            attributes.push(input.parse()?);
+        }
+
+        let mut children = Vec::new();
+
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+        } else {
+            input.parse::<Token![>]>()?;
+
+            while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+                if input.is_empty() {
+                    return Err(syn::Error::new(
+                        name.span,
+                        format!("Missing closing tag `</{}>`", name.name),
+                    ));
+                }
+
+                children.push(input.parse()?);
+            }
+
+            input.parse::<Token![<]>()?;
+            input.parse::<Token![/]>()?;
+            let closing: HtmlName = input.parse()?;
+
+            if closing.name != name.name {
+                return Err(syn::Error::new(
+                    closing.span,
+                    format!("Expected closing tag `</{}>`", name.name),
+                ));
+            }
+
+            input.parse::<Token![>]>()?;
+        }
+
+        Ok(Self {
+            name,
+            attributes,
+            children,
+        })
+    }
+}
+
+enum Attribute {
+    Value {
+        name: HtmlName,
+        value: Option<Value>,
+    },
+    Event {
+        name: HtmlName,
+        handler: Expr,
+    },
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: HtmlName = input.parse()?;
+
+        if name.name == "on" && input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let content;
+            braced!(content in input);
+
+            return Ok(Self::Event {
+                name,
+                handler: content.parse()?,
+            });
+        }
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self::Value { name, value })
+    }
+}
+
+enum Value {
+    Lit(Lit),
+    Expr(Expr),
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            Ok(Self::Expr(content.parse()?))
+        } else {
+            Ok(Self::Lit(input.parse()?))
+        }
+    }
+}
+
+enum Child {
+    Element(Element),
+    Text(LitStr),
+    Expr(Expr),
+    Children(Expr),
+}
+
+impl Parse for Child {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            Ok(Self::Element(input.parse()?))
+        } else if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+
+            if content.peek(Token![..]) {
+                content.parse::<Token![..]>()?;
+                Ok(Self::Children(content.parse()?))
+            } else {
+                Ok(Self::Expr(content.parse()?))
+            }
+        } else {
+            Ok(Self::Text(input.parse()?))
+        }
+    }
+}
+
+/// A tag, attribute or event name, which can contain `-`.
+struct HtmlName {
+    name: String,
+    span: Span,
+}
+
+impl HtmlName {
+    /// The rust identifier for the name.
+    ///
+    /// `-` is replaced with `_`, and keywords are converted to raw
+    /// identifiers.
+    fn ident(&self) -> Ident {
+        let name = self.name.replace('-', "_");
+
+        if syn::parse_str::<Ident>(&name).is_ok() {
+            Ident::new(&name, self.span)
+        } else {
+            Ident::new_raw(&name, self.span)
+        }
+    }
+}
+
+impl Parse for HtmlName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first = input.call(Ident::parse_any)?;
+        let span = first.span();
+        let mut name = first.to_string();
+
+        while input.peek(Token![-]) && input.peek2(Ident::peek_any) {
+            input.parse::<Token![-]>()?;
+            name.push('-');
+            name.push_str(&input.call(Ident::parse_any)?.to_string());
+        }
+
+        Ok(Self { name, span })
+    }
+}
+
+pub fn code_gen(Input(element): Input) -> TokenStream {
+    let element = element_tokens(&element, false);
+
+    quote!({
+        #[allow(unused_imports)]
+        use ::silkenweb::{
+            elements::{
+                svg::attributes::{
+                    AnimationTiming as _, AnimationValue as _, ConditionalProcessing as _,
+                    Core as _, FilterPrimitives as _, OtherAnimation as _, Presentation as _,
+                    TransferFunction as _,
+                },
+                AriaElement as _, ElementEvents as _, HtmlElement as _, HtmlElementEvents as _,
+            },
+            node::element::{Element as _, ParentElement as _},
+        };
+
+        #element
+    })
+}
+
+fn element_tokens(element: &Element, in_svg: bool) -> TokenStream {
+    let in_svg = in_svg || element.name.name == "svg";
+    let module = if in_svg { quote!(svg) } else { quote!(html) };
+    let tag = element.name.ident();
+    let mut tokens = quote_spanned!(element.name.span=> ::silkenweb::elements::#module::#tag());
+
+    for attribute in &element.attributes {
+        tokens = match attribute {
+            Attribute::Value { name, value } => attribute_tokens(tokens, name, value.as_ref()),
+            Attribute::Event { name, handler } => {
+                let method = Ident::new(&format!("on_{}", name.name.replace('-', "_")), name.span);
+                quote!(#tokens.#method(#handler))
+            }
+        };
+    }
+
+    for child in &element.children {
+        tokens = match child {
+            Child::Element(child) => {
+                let child = element_tokens(child, in_svg);
+                quote!(#tokens.child(#child))
+            }
+            Child::Text(text) => quote!(#tokens.text(#text)),
+            Child::Expr(expr) => quote_spanned!(expr.span()=>
+                ::silkenweb::macros::HtmlChild::add_to(#expr, #tokens)
+            ),
+            Child::Children(children) => quote!(#tokens.children(#children)),
+        };
+    }
+
+    tokens
+}
+
+fn attribute_tokens(element: TokenStream, name: &HtmlName, value: Option<&Value>) -> TokenStream {
+    let span = name.span;
+
+    if name.name == "class" {
+        if let Some(Value::Lit(Lit::Str(classes))) = value {
+            let classes = classes.value();
+            let classes = classes.split_whitespace();
+            return quote_spanned!(span=> #element.classes([#(#classes),*]));
+        }
+    }
+
+    let value = match value {
+        Some(Value::Lit(lit)) => quote!(#lit),
+        Some(Value::Expr(expr)) => quote!(#expr),
+        None => quote_spanned!(span=> true),
+    };
+
+    if name.name.starts_with("data-") {
+        let data_name = &name.name;
+        return quote_spanned!(span=> #element.attribute(#data_name, #value));
+    }
+
+    let method = name.ident();
+    quote!(#element.#method(#value))
+}
@@ -3,7 +3,7 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_error::{abort, abort_call_site, proc_macro_error};
 use quote::quote;
-use silkenweb_css::{Css, NameMapping};
+use silkenweb_css::{Css, NameMapping, PropertyRule};
 use syn::{
     parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed,
     FieldsUnnamed, Ident, Index, LitBool,
@@ -11,6 +11,8 @@ use syn::{
 
 use crate::parse::Input;
 
+mod html;
+mod manifest;
 mod parse;
 
 macro_rules! derive_empty(
@@ -46,6 +48,11 @@ derive_empty!(
     derive_element_events(elements, ElementEvents);
 );
 
+#[cfg(feature = "htmx")]
+derive_empty!(
+    derive_htmx_element(htmx, HtmxElement);
+);
+
 #[proc_macro_derive(ChildElement, attributes(child_element))]
 #[proc_macro_error]
 pub fn derive_child_element(item: TokenStream) -> TokenStream {
@@ -181,6 +188,10 @@ pub fn derive_element(item: TokenStream) -> TokenStream {
                 Self{#target: self.#target.spawn_future(future) #other_fields}
             }
 
+            fn task_group(self, group: ::silkenweb::task::TaskGroup) -> Self {
+                Self{#target: self.#target.task_group(group) #other_fields}
+            }
+
             fn on(self, name: &'static str, f: impl FnMut(::silkenweb::macros::JsValue) + 'static) -> Self {
                 Self{#target: self.#target.on(name, f) #other_fields}
             }
@@ -292,6 +303,9 @@ pub fn css(input: TokenStream) -> TokenStream {
         exclude_prefixes,
         validate,
         auto_mount,
+        scoped,
+        bundled,
+        typed,
         transpile,
     } = parse_macro_input!(input);
 
@@ -299,9 +313,16 @@ pub fn css(input: TokenStream) -> TokenStream {
         .transpile(validate, transpile.map(Transpile::into))
         .unwrap_or_else(|e| abort_call_site!(e));
 
+    let scope = scoped.then(|| {
+        let scope = source.scope_attribute();
+        source.scope(&scope);
+        scope
+    });
+
     let variables = source.variable_names().map(|variable| NameMapping {
         plain: variable.clone(),
         mangled: format!("--{variable}"),
+        composes: Vec::new(),
     });
 
     let classes = name_mappings.unwrap_or_else(|| {
@@ -310,19 +331,66 @@ pub fn css(input: TokenStream) -> TokenStream {
             .map(|class| NameMapping {
                 plain: class.clone(),
                 mangled: class,
+                composes: Vec::new(),
             })
             .collect()
     });
 
     let classes = only_matching_prefixes(&include_prefixes, &exclude_prefixes, classes.into_iter());
     let variables = only_matching_prefixes(&include_prefixes, &exclude_prefixes, variables);
+    let options = CodeGenOptions {
+        public,
+        auto_mount,
+        typed,
+        bundled,
+        scope,
+    };
 
     if let Some(prefix) = prefix {
         let classes = strip_prefixes(&prefix, classes);
         let variables = strip_prefixes(&prefix, variables);
-        code_gen(&source, public, auto_mount, classes, variables)
+        code_gen(&source, options, classes, variables)
+    } else {
+        code_gen(&source, options, classes, variables)
+    }
+}
+
+fn define_custom_property(
+    name: &NameMapping,
+    rule: &PropertyRule,
+    auto_mount: bool,
+) -> proc_macro2::TokenStream {
+    // Any syntax we don't have a type for, including a missing syntax, falls
+    // back to an untyped `String` property.
+    let value_type = match rule.syntax.as_deref().unwrap_or("*") {
+        "<color>" => quote!(::silkenweb::custom_property::Color),
+        "<length>" => quote!(::silkenweb::custom_property::Length),
+        "<percentage>" => quote!(::silkenweb::custom_property::Percentage),
+        "<length-percentage>" => quote!(::silkenweb::custom_property::LengthPercentage),
+        "<number>" => quote!(f64),
+        "<integer>" => quote!(i64),
+        "<angle>" => quote!(::silkenweb::custom_property::Angle),
+        "<time>" => quote!(::silkenweb::custom_property::Time),
+        _ => quote!(::std::string::String),
+    };
+    let NameMapping { plain, mangled, .. } = name;
+    let ident = entity_ident(plain);
+    let initial_value = match &rule.initial_value {
+        Some(initial_value) => quote!(::std::option::Option::Some(#initial_value)),
+        None => quote!(::std::option::Option::None),
+    };
+    let typ = quote!(::silkenweb::custom_property::CustomProperty<#value_type>);
+    let value = quote!(::silkenweb::custom_property::CustomProperty::new(#mangled, #initial_value));
+
+    if auto_mount {
+        let ident = Ident::new(&ident.to_lowercase(), Span::call_site());
+        quote!(pub fn #ident() -> #typ {
+            super::stylesheet::mount();
+            #value
+        })
     } else {
-        code_gen(&source, public, auto_mount, classes, variables)
+        let ident = Ident::new(&ident.to_uppercase(), Span::call_site());
+        quote!(pub const #ident: #typ = #value;)
     }
 }
 
@@ -350,11 +418,9 @@ fn strip_prefixes<'a>(
     prefix: &'a str,
     names: impl Iterator<Item = NameMapping> + 'a,
 ) -> impl Iterator<Item = NameMapping> + 'a {
-    names.filter_map(move |NameMapping { plain, mangled }| {
-        plain
-            .strip_prefix(prefix)
-            .map(str::to_string)
-            .map(|plain| NameMapping { plain, mangled })
+    names.filter_map(move |mapping| {
+        let plain = mapping.plain.strip_prefix(prefix)?.to_string();
+        Some(NameMapping { plain, ..mapping })
     })
 }
 
@@ -362,31 +428,80 @@ fn any_prefix_matches(x: &str, prefixes: &[String]) -> bool {
     prefixes.iter().any(|prefix| x.starts_with(prefix))
 }
 
-fn code_gen(
-    source: &Css,
+struct CodeGenOptions {
     public: bool,
     auto_mount: bool,
+    typed: bool,
+    bundled: bool,
+    scope: Option<String>,
+}
+
+fn code_gen(
+    source: &Css,
+    options: CodeGenOptions,
     classes: impl Iterator<Item = NameMapping>,
     variables: impl Iterator<Item = NameMapping>,
 ) -> TokenStream {
-    let classes = classes.map(|name| define_css_entity(name, auto_mount));
-    let variables = variables.map(|name| define_css_entity(name, auto_mount));
+    let CodeGenOptions {
+        public,
+        auto_mount,
+        typed,
+        bundled,
+        scope,
+    } = options;
+    let classes: Vec<NameMapping> = classes.collect();
+    let typed_classes = typed.then(|| define_typed_classes(&classes, auto_mount));
+    let classes = classes
+        .into_iter()
+        .map(|name| define_css_entity(name, auto_mount));
+    let variables: Vec<NameMapping> = variables.collect();
+    let property_rules = source.property_rules();
+    let custom_properties: Vec<_> = variables
+        .iter()
+        .filter_map(|name| {
+            let rule = property_rules
+                .iter()
+                .find(|rule| name.mangled.strip_prefix("--") == Some(rule.name.as_str()))?;
+            Some(define_custom_property(name, rule, auto_mount))
+        })
+        .collect();
+    let variables = variables
+        .into_iter()
+        .map(|name| define_css_entity(name, auto_mount));
 
-    let dependency = source.dependency().into_iter();
+    let dependency = source.dependencies();
     let content = source.content();
     let visibility = if public { quote!(pub) } else { quote!() };
+    // Bundled stylesheets are linked from the page, so aren't mounted.
+    let mount_stylesheet = (!bundled).then(|| quote!(D::mount_stylesheet(ID, text());));
+    let scope = scope.map(|scope| {
+        quote!(
+            pub const SCOPE: &str = #scope;
+
+            pub fn scoped<T>(f: impl FnOnce() -> T) -> T {
+                mount();
+                ::silkenweb::node::element::with_style_scope(SCOPE, f)
+            }
+        )
+    });
 
     quote!(
         #(const _: &[u8] = ::std::include_bytes!(#dependency);)*
 
         #visibility mod class {
             #(#classes)*
+
+            #typed_classes
         }
 
         #visibility mod var {
             #(#variables)*
         }
 
+        #visibility mod property {
+            #(#custom_properties)*
+        }
+
         #visibility mod stylesheet {
             use ::silkenweb::document::Document;
 
@@ -396,41 +511,34 @@ fn code_gen(
             }
 
             pub fn mount_dom<D: Document>() {
-                use ::std::{panic::Location, sync::Once};
-                use ::silkenweb::{
-                    document::{Document, DocumentHead},
-                    node::element::ParentElement,
-                    elements::html::style,
-                };
-
-                static INIT: Once = Once::new();
-
-                INIT.call_once(|| {
-                    let location = Location::caller();
-                    let head = DocumentHead::new().child(style().text(text()));
-
-                    D::mount_in_head(
-                        &format!(
-                            "silkenweb-style:{}:{}:{}",
-                            location.file(),
-                            location.line(),
-                            location.column()
-                        ),
-                        head
-                    );
-                });
+                #mount_stylesheet
             }
 
+            pub const ID: &str = ::std::concat!(
+                "silkenweb-style:",
+                ::std::file!(),
+                ":",
+                ::std::line!(),
+                ":",
+                ::std::column!()
+            );
+
             pub fn text() -> &'static str {
                 #content
             }
+
+            #scope
         }
     )
     .into()
 }
 
 fn define_css_entity(name: NameMapping, auto_mount: bool) -> proc_macro2::TokenStream {
-    let NameMapping { plain, mangled } = name;
+    let NameMapping {
+        plain,
+        mangled,
+        composes,
+    } = name;
 
     if !plain.starts_with(char::is_alphabetic) {
         abort_call_site!(
@@ -439,20 +547,96 @@ fn define_css_entity(name: NameMapping, auto_mount: bool) -> proc_macro2::TokenS
         );
     }
 
-    let ident = plain.replace(|c: char| !c.is_alphanumeric(), "_");
+    let ident = entity_ident(&plain);
+    let value = if composes.is_empty() {
+        quote!(#mangled)
+    } else {
+        quote!(&[#mangled, #(#composes),*])
+    };
 
     if auto_mount {
         let ident = Ident::new(&ident.to_lowercase(), Span::call_site());
-        quote!(pub fn #ident() -> &'static str {
+        let typ = if composes.is_empty() {
+            quote!(&'static str)
+        } else {
+            quote!(&'static [&'static str])
+        };
+
+        quote!(pub fn #ident() -> #typ {
             super::stylesheet::mount();
-            #mangled
+            #value
         })
     } else {
         let ident = Ident::new(&ident.to_uppercase(), Span::call_site());
-        quote!(pub const #ident: &str = #mangled;)
+        let typ = if composes.is_empty() {
+            quote!(&str)
+        } else {
+            quote!(&[&str])
+        };
+
+        quote!(pub const #ident: #typ = #value;)
     }
 }
 
+fn define_typed_classes(classes: &[NameMapping], auto_mount: bool) -> proc_macro2::TokenStream {
+    let classes = classes.iter().map(|name| {
+        let ident = entity_ident(&name.plain);
+        let class_names = [name.mangled.as_str()]
+            .into_iter()
+            .chain(name.composes.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let value = quote!(Self(::std::borrow::Cow::Borrowed(#class_names)));
+
+        if auto_mount {
+            let ident = Ident::new(&ident.to_lowercase(), Span::call_site());
+            quote!(pub fn #ident() -> Self {
+                super::stylesheet::mount();
+                #value
+            })
+        } else {
+            let ident = Ident::new(&ident.to_uppercase(), Span::call_site());
+            quote!(pub const #ident: Self = #value;)
+        }
+    });
+
+    quote!(
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub struct Class(::std::borrow::Cow<'static, str>);
+
+        impl Class {
+            #(#classes)*
+
+            pub fn dynamic(name: impl ::std::convert::Into<::std::string::String>) -> Self {
+                Self(::std::borrow::Cow::Owned(name.into()))
+            }
+        }
+
+        impl ::silkenweb::typed_class::TypedClass for Class {
+            fn name(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl ::silkenweb::value::Value for Class {}
+    )
+}
+
+fn entity_ident(plain: &str) -> String {
+    plain.replace(|c: char| !c.is_alphanumeric(), "_")
+}
+
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    html::code_gen(parse_macro_input!(input)).into()
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn custom_elements_manifest(input: TokenStream) -> TokenStream {
+    manifest::code_gen(parse_macro_input!(input)).into()
+}
+
 /// Convert a rust ident to an html ident by stripping any "r#" prefix and
 /// replacing '_' with '-'.
 #[doc(hidden)]
@@ -0,0 +1,342 @@
+//! Generate custom element types from a [Custom Elements Manifest].
+//!
+//! [Custom Elements Manifest]: https://github.com/webcomponents/custom-elements-manifest
+use std::{env, fs, path::PathBuf};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::abort_call_site;
+use quote::quote;
+use serde::Deserialize;
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, LitStr,
+};
+
+use crate::parse::{kw, parameter, parse_fields};
+
+pub struct Input {
+    path: String,
+    elements: Option<Vec<String>>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Self {
+                path: input.parse::<LitStr>()?.value(),
+                elements: None,
+            });
+        }
+
+        let mut path = None;
+        let mut elements = None;
+
+        parse_fields(input, |field, input| {
+            Ok(parameter(kw::path, field, input, &mut path)?
+                || parameter(kw::elements, field, input, &mut elements)?)
+        })?;
+
+        let Some(path) = path else {
+            abort_call_site!("Must specify a `path` parameter")
+        };
+
+        Ok(Self { path, elements })
+    }
+}
+
+pub fn code_gen(input: Input) -> TokenStream {
+    let path = PathBuf::from(
+        env::var("CARGO_MANIFEST_DIR")
+            .unwrap_or_else(|e| abort_call_site!("Couldn't read 'CARGO_MANIFEST_DIR': {}", e)),
+    )
+    .join(&input.path);
+    let path = path
+        .to_str()
+        .unwrap_or_else(|| abort_call_site!("Couldn't convert filename to string: '{:?}'", path));
+    let manifest = fs::read_to_string(path)
+        .unwrap_or_else(|e| abort_call_site!("Couldn't read file '{}': {}", path, e));
+    let manifest: Manifest = serde_json::from_str(&manifest)
+        .unwrap_or_else(|e| abort_call_site!("Couldn't parse '{}': {}", path, e));
+
+    let mut declarations: Vec<Declaration> = manifest
+        .modules
+        .into_iter()
+        .flat_map(|module| module.declarations)
+        .filter(|declaration| declaration.custom_element && declaration.tag_name.is_some())
+        .collect();
+
+    if let Some(elements) = &input.elements {
+        for element in elements {
+            if !declarations
+                .iter()
+                .any(|declaration| declaration.tag_name.as_ref() == Some(element))
+            {
+                abort_call_site!("Custom element '{}' isn't in the manifest", element);
+            }
+        }
+
+        declarations.retain(|declaration| {
+            declaration
+                .tag_name
+                .as_ref()
+                .is_some_and(|tag_name| elements.contains(tag_name))
+        });
+    }
+
+    let elements = declarations.iter().map(define_element);
+
+    quote!(
+        const _: &[u8] = ::std::include_bytes!(#path);
+
+        #(#elements)*
+    )
+}
+
+fn define_element(declaration: &Declaration) -> TokenStream {
+    let tag_name = declaration.tag_name.as_deref().unwrap();
+    let name = rust_ident(tag_name);
+    let doc = docs(&declaration.description);
+
+    let attributes = declaration.attributes.iter().map(|attribute| {
+        let ident = rust_ident(&attribute.name);
+        let text_name = &attribute.name;
+        let doc = docs(&attribute.description);
+        let typ = match type_text(&attribute.r#type) {
+            Some("boolean") => quote!(bool),
+            Some("number") => quote!(f64),
+            _ => quote!(String),
+        };
+
+        quote!(#doc #ident(#text_name): #typ)
+    });
+
+    let events = declaration
+        .events
+        .iter()
+        .filter(|event| !event.name.is_empty())
+        .map(|event| {
+            let ident = rust_ident(&event.name);
+            let text_name = &event.name;
+            let doc = docs(&event.description);
+
+            quote!(
+                #doc #ident(#text_name):
+                    ::silkenweb::elements::CustomEvent<::silkenweb::macros::JsValue>
+            )
+        });
+
+    let camel_name = Ident::new(&tag_name.to_upper_camel_case(), Span::call_site());
+    let properties = declaration
+        .members
+        .iter()
+        .filter(|member| member.is_settable_field())
+        .filter_map(|member| {
+            let typ = match type_text(&member.r#type) {
+                Some("boolean") => quote!(bool),
+                Some("number") => quote!(f64),
+                Some("string") => quote!(String),
+                _ => return None,
+            };
+            let setter = Ident::new(
+                &format!("set_{}", member.name.to_snake_case()),
+                Span::call_site(),
+            );
+            let property_name = &member.name;
+            let doc = docs(&member.description);
+
+            Some(quote!(
+                #doc
+                pub fn #setter(
+                    self,
+                    value: impl ::silkenweb::value::SignalOrValue<Item = #typ>
+                ) -> Self {
+                    fn set_property(element: &::silkenweb::macros::web_sys::HtmlElement, value: #typ) {
+                        ::silkenweb::macros::UnwrapThrowExt::unwrap_throw(
+                            ::silkenweb::macros::js_sys::Reflect::set(
+                                element,
+                                &::silkenweb::macros::JsValue::from_str(#property_name),
+                                &::silkenweb::macros::JsValue::from(value),
+                            )
+                        );
+                    }
+
+                    ::silkenweb::value::RefSignalOrValue::select(
+                        value,
+                        |element, value| ::silkenweb::node::element::Element::map_element(
+                            element,
+                            move |element| set_property(element, value),
+                        ),
+                        |element, value| ::silkenweb::node::element::Element::map_element_signal(
+                            element,
+                            value,
+                            set_property,
+                        ),
+                        self,
+                    )
+                }
+            ))
+        });
+
+    let slots = declaration
+        .slots
+        .iter()
+        .filter(|slot| !slot.name.is_empty())
+        .map(|slot| {
+            let method = Ident::new(
+                &format!("{}_slot", slot.name.to_snake_case()),
+                Span::call_site(),
+            );
+            let slot_name = &slot.name;
+            let doc = docs(&slot.description);
+
+            quote!(
+                #doc
+                pub fn #method(
+                    self,
+                    child: impl ::silkenweb::elements::HtmlElement
+                        + ::silkenweb::node::ChildNode<Dom>
+                ) -> Self {
+                    ::silkenweb::node::element::ParentElement::child(
+                        self,
+                        ::silkenweb::elements::HtmlElement::slot(child, #slot_name),
+                    )
+                }
+            )
+        });
+    // `child` and `text` add to the default slot, so only provide them if there
+    // is one.
+    let parent = declaration
+        .slots
+        .iter()
+        .any(|slot| slot.name.is_empty())
+        .then(|| quote!(::silkenweb::parent_element!(#name);));
+
+    quote!(
+        ::silkenweb::custom_html_element!(
+            #doc
+            #name(#tag_name) = {
+                dom_type: ::silkenweb::macros::web_sys::HtmlElement;
+
+                attributes {
+                    #(#attributes),*
+                };
+
+                custom_events {
+                    #(#events),*
+                };
+            }
+        );
+
+        #parent
+
+        impl<Dom: ::silkenweb::dom::Dom> #camel_name<Dom> {
+            #(#properties)*
+            #(#slots)*
+        }
+    )
+}
+
+fn docs(description: &Option<String>) -> TokenStream {
+    description
+        .iter()
+        .flat_map(|description| description.lines())
+        .map(|line| {
+            let line = format!(" {line}");
+            quote!(#[doc = #line])
+        })
+        .collect()
+}
+
+fn type_text(typ: &Option<Type>) -> Option<&str> {
+    typ.as_ref().map(|typ| typ.text.trim())
+}
+
+/// Convert a manifest name to a snake case rust identifier.
+///
+/// Keywords are converted to raw identifiers.
+fn rust_ident(name: &str) -> Ident {
+    let snake_name = name.to_snake_case();
+
+    syn::parse_str::<Ident>(&snake_name).unwrap_or_else(|_| {
+        syn::parse_str::<Ident>(&format!("r#{snake_name}"))
+            .unwrap_or_else(|_| abort_call_site!("'{}' isn't a valid identifier", name))
+    })
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    modules: Vec<Module>,
+}
+
+#[derive(Deserialize)]
+struct Module {
+    #[serde(default)]
+    declarations: Vec<Declaration>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Declaration {
+    #[serde(default)]
+    custom_element: bool,
+    tag_name: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    attributes: Vec<Attribute>,
+    #[serde(default)]
+    members: Vec<Member>,
+    #[serde(default)]
+    events: Vec<Event>,
+    #[serde(default)]
+    slots: Vec<Slot>,
+}
+
+#[derive(Deserialize)]
+struct Attribute {
+    name: String,
+    description: Option<String>,
+    r#type: Option<Type>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    kind: String,
+    name: String,
+    description: Option<String>,
+    r#type: Option<Type>,
+    privacy: Option<String>,
+    #[serde(default)]
+    r#static: bool,
+    #[serde(default)]
+    readonly: bool,
+}
+
+impl Member {
+    fn is_settable_field(&self) -> bool {
+        self.kind == "field"
+            && !self.r#static
+            && !self.readonly
+            && self.privacy.as_deref().unwrap_or("public") == "public"
+    }
+}
+
+#[derive(Deserialize)]
+struct Event {
+    #[serde(default)]
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Slot {
+    #[serde(default)]
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Type {
+    text: String,
+}
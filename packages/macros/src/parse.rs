@@ -11,7 +11,7 @@ use syn::{
     LitInt, LitStr,
 };
 
-trait Named {
+pub(crate) trait Named {
     fn name() -> &'static str;
 }
 
@@ -27,7 +27,7 @@ macro_rules! keyword {
     };
 }
 
-mod kw {
+pub(crate) mod kw {
     use syn::custom_keyword;
 
     use super::Named;
@@ -41,12 +41,16 @@ mod kw {
     keyword!(exclude_prefixes);
     keyword!(validate);
     keyword!(auto_mount);
+    keyword!(scoped);
+    keyword!(bundled);
+    keyword!(typed);
     keyword!(transpile);
     keyword!(minify);
     keyword!(pretty);
     keyword!(modules);
     keyword!(nesting);
     keyword!(browsers);
+    keyword!(elements);
 }
 
 mod functions {
@@ -55,7 +59,7 @@ mod functions {
     custom_keyword!(concat);
 }
 
-trait ParseValue: Sized {
+pub(crate) trait ParseValue: Sized {
     fn parse(input: ParseStream) -> syn::Result<Self>;
 }
 
@@ -102,6 +106,9 @@ pub struct Input {
     pub exclude_prefixes: Vec<String>,
     pub validate: bool,
     pub auto_mount: bool,
+    pub scoped: bool,
+    pub bundled: bool,
+    pub typed: bool,
     pub transpile: Option<Transpile>,
 }
 
@@ -117,6 +124,9 @@ impl Parse for Input {
                 exclude_prefixes: Vec::new(),
                 validate: false,
                 auto_mount: false,
+                scoped: false,
+                bundled: false,
+                typed: false,
                 transpile: None,
             });
         }
@@ -130,6 +140,9 @@ impl Parse for Input {
         let mut exclude_prefixes = None;
         let mut validate = false;
         let mut auto_mount = false;
+        let mut scoped = false;
+        let mut bundled = false;
+        let mut typed = false;
         let mut transpile = None;
 
         parse_fields(input, |field, input| {
@@ -142,6 +155,9 @@ impl Parse for Input {
                 || parameter(kw::exclude_prefixes, field, input, &mut exclude_prefixes)?
                 || flag(kw::validate, field, input, &mut validate)?
                 || flag(kw::auto_mount, field, input, &mut auto_mount)?
+                || flag(kw::scoped, field, input, &mut scoped)?
+                || flag(kw::bundled, field, input, &mut bundled)?
+                || flag(kw::typed, field, input, &mut typed)?
                 || parameter(kw::transpile, field, input, &mut transpile)?)
         })?;
 
@@ -163,6 +179,9 @@ impl Parse for Input {
             exclude_prefixes: exclude_prefixes.unwrap_or_default(),
             validate,
             auto_mount,
+            scoped,
+            bundled,
+            typed,
             transpile,
         })
     }
@@ -252,7 +271,7 @@ fn version_component(input: &syn::parse::ParseBuffer) -> Result<u8, syn::Error>
     input.parse::<LitInt>()?.base10_parse()
 }
 
-fn parameter<Keyword, KeywordToken, T, V>(
+pub(crate) fn parameter<Keyword, KeywordToken, T, V>(
     keyword: Keyword,
     field: &Lookahead1,
     input: ParseStream,
@@ -326,7 +345,7 @@ fn parse_comma_delimited<T>(
     Ok(result)
 }
 
-fn parse_fields(
+pub(crate) fn parse_fields(
     input: ParseStream,
     mut parser: impl FnMut(&Lookahead1, ParseStream) -> syn::Result<bool>,
 ) -> syn::Result<()> {
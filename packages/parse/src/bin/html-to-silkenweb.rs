@@ -0,0 +1,47 @@
+//! Convert an HTML file to Silkenweb builder code.
+//!
+//! Usage: `html-to-silkenweb [FILE]`
+//!
+//! The HTML is read from `FILE`, or stdin if no file is given, and the Rust
+//! code is written to stdout.
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use silkenweb_parse::html_to_rust;
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let file = args.next();
+
+    if args.next().is_some() || file.as_deref().is_some_and(|arg| arg.starts_with('-')) {
+        eprintln!("Usage: html-to-silkenweb [FILE]");
+        return ExitCode::FAILURE;
+    }
+
+    let html = match &file {
+        Some(file) => {
+            fs::read_to_string(file).map_err(|e| format!("Couldn't read file '{file}': {e}"))
+        }
+        None => {
+            let mut html = String::new();
+            io::stdin()
+                .read_to_string(&mut html)
+                .map(|_| html)
+                .map_err(|e| format!("Couldn't read stdin: {e}"))
+        }
+    };
+
+    match html {
+        Ok(html) => {
+            print!("{}", html_to_rust(&html));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
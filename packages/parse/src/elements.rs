@@ -0,0 +1,134 @@
+//! Find the typed builders in [`silkenweb::elements`], using the metadata
+//! generated from their definitions.
+use std::str::FromStr;
+
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{format_ident, quote};
+use silkenweb::elements::metadata::{self, AttributeType};
+
+pub const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// A typed element builder.
+pub struct TypedElement {
+    module: Ident,
+    snake_name: Ident,
+    camel_name: Ident,
+    metadata: &'static metadata::Element,
+}
+
+impl TypedElement {
+    /// Find the typed builder for an element, given its namespace and local
+    /// name.
+    pub fn find(ns: &str, name: &str) -> Option<Self> {
+        let (module, elements) = match ns {
+            HTML_NAMESPACE => ("html", metadata::HTML_ELEMENTS),
+            SVG_NAMESPACE => ("svg", metadata::SVG_ELEMENTS),
+            _ => return None,
+        };
+        let metadata = elements.iter().find(|element| element.name == name)?;
+        let snake_name = rust_ident(metadata.fn_name);
+        let camel_name: String = metadata
+            .fn_name
+            .trim_start_matches("r#")
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        Some(Self {
+            module: format_ident!("{module}"),
+            snake_name,
+            camel_name: format_ident!("{camel_name}"),
+            metadata,
+        })
+    }
+
+    pub fn is_parent(&self) -> bool {
+        self.metadata.is_parent
+    }
+
+    pub fn is_svg(&self) -> bool {
+        self.module == "svg"
+    }
+
+    /// The name of the function that constructs the element.
+    pub fn snake_name(&self) -> &Ident {
+        &self.snake_name
+    }
+
+    pub fn element_type(&self, dom_type: &TokenStream) -> TokenStream {
+        let Self {
+            module, camel_name, ..
+        } = self;
+        quote!(::silkenweb::elements::#module::#camel_name<#dom_type>)
+    }
+
+    pub fn constructor(&self, dom_type: &TokenStream) -> TokenStream {
+        let Self {
+            module, snake_name, ..
+        } = self;
+        quote!(::silkenweb::elements::#module::#snake_name::<#dom_type>())
+    }
+
+    /// The typed method to set an attribute, and its argument.
+    ///
+    /// This is `None` if the element has no typed method for the attribute,
+    /// or if `value` wouldn't render exactly the same through the typed
+    /// method. For example, `start="05"` on an `<ol>` is left as text,
+    /// because `ol().start(5u32)` would render `start="5"`.
+    pub fn attribute(&self, name: &str, value: &str) -> Option<(Ident, TokenStream)> {
+        let attribute = self.metadata.attribute(name)?;
+        let value = match attribute.typ {
+            AttributeType::String => Literal::string(value),
+            AttributeType::Bool => {
+                // `true` renders as an empty attribute.
+                return value
+                    .is_empty()
+                    .then(|| (rust_ident(attribute.method), quote!(true)));
+            }
+            AttributeType::F32 => number(value, Literal::f32_suffixed)?,
+            AttributeType::F64 => number(value, Literal::f64_suffixed)?,
+            AttributeType::I32 => number(value, Literal::i32_suffixed)?,
+            AttributeType::I64 => number(value, Literal::i64_suffixed)?,
+            AttributeType::U32 => number(value, Literal::u32_suffixed)?,
+            AttributeType::U64 => number(value, Literal::u64_suffixed)?,
+            AttributeType::Other => return None,
+        };
+
+        Some((rust_ident(attribute.method), quote!(#value)))
+    }
+}
+
+/// Parse a number, if it renders back to `value`.
+///
+/// Floats like `inf` and `NaN` aren't numeric literals, so only digits, signs,
+/// decimal points and exponents are allowed.
+fn number<T: FromStr + ToString>(
+    value: &str,
+    literal: impl FnOnce(T) -> Literal,
+) -> Option<Literal> {
+    if !value
+        .bytes()
+        .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b))
+    {
+        return None;
+    }
+
+    let number: T = value.parse().ok()?;
+    (number.to_string() == value).then(|| literal(number))
+}
+
+/// Convert a name from [`metadata`] to an identifier, allowing for raw
+/// identifiers like `r#type`.
+fn rust_ident(name: &str) -> Ident {
+    match name.strip_prefix("r#") {
+        Some(name) => Ident::new_raw(name, Span::call_site()),
+        None => Ident::new(name, Span::call_site()),
+    }
+}
@@ -0,0 +1,43 @@
+//! Runtime support for `html_file!(.., hot_reload)` in `silkenweb-inline-html`.
+use std::{cell::RefCell, collections::HashMap, fs, path::PathBuf};
+
+use silkenweb::{dom::Dom, node::Node};
+
+use crate::html_to_nodes;
+
+thread_local! {
+    static PATH_OVERRIDES: RefCell<HashMap<String, PathBuf>> = RefCell::new(HashMap::new());
+}
+
+/// Read the element in the file at `path`.
+///
+/// This is `None` if the file can't be read, or doesn't contain exactly one
+/// element.
+pub fn read_node<D: Dom>(path: &str) -> Option<Node<D>> {
+    let path = PATH_OVERRIDES
+        .with(|overrides| overrides.borrow().get(path).cloned())
+        .unwrap_or_else(|| PathBuf::from(path));
+    let html = fs::read_to_string(path).ok()?;
+    let mut nodes = html_to_nodes(&html);
+
+    (nodes.len() == 1).then(|| nodes.remove(0))
+}
+
+/// Read `to` instead of `from` while `f` runs on this thread.
+///
+/// This lets tests edit a copy of a template, rather than the template that was
+/// compiled in.
+pub fn with_path_override<T>(from: &str, to: impl Into<PathBuf>, f: impl FnOnce() -> T) -> T {
+    struct RemoveOverride<'a>(&'a str);
+
+    impl Drop for RemoveOverride<'_> {
+        fn drop(&mut self) {
+            PATH_OVERRIDES.with(|overrides| overrides.borrow_mut().remove(self.0));
+        }
+    }
+
+    PATH_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(from.to_string(), to.into()));
+    let _remove_override = RemoveOverride(from);
+
+    f()
+}
@@ -13,10 +13,21 @@
 //! - If any errors are present, a best effort is made to parse the HTML.
 //! - Any empty text nodes are removed.
 //! - Attributes are sorted to make the result more testable.
+//! - Untrusted HTML can be sanitized with [`html_to_sanitized_nodes`].
 extern crate proc_macro;
 
+mod elements;
+#[doc(hidden)]
+pub mod hot_reload;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+mod rust_code;
+pub mod sanitize;
+
+use elements::TypedElement;
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use sanitize::Policy;
 use silkenweb::{
     cfg_browser,
     dom::Dom,
@@ -26,6 +37,7 @@ use silkenweb::{
     },
 };
 
+#[derive(Clone)]
 enum DomNode {
     Element {
         ns: String,
@@ -54,6 +66,27 @@ pub fn html_to_nodes<D: Dom>(html: &str) -> Vec<Node<D>> {
     tree_to_nodes(arch::parse_html(html))
 }
 
+/// Convert untrusted HTML to Silkenweb nodes, using `policy` to sanitize it.
+///
+/// This is like [`html_to_nodes`], but elements and attributes are removed
+/// unless `policy` allows them. See the [`sanitize`] module for details.
+///
+/// # Example
+///
+/// ```
+/// # use silkenweb::node::Node;
+/// # use silkenweb_parse::{html_to_sanitized_nodes, sanitize::Policy};
+/// let nodes: Vec<Node> = html_to_sanitized_nodes(
+///     r#"<p>Hello <img src="x.png" onerror="steal()"></p>"#,
+///     &Policy::default(),
+/// );
+///
+/// assert_eq!(nodes[0].to_string(), r#"<p>Hello <img src="x.png"></p>"#);
+/// ```
+pub fn html_to_sanitized_nodes<D: Dom>(html: &str, policy: &Policy) -> Vec<Node<D>> {
+    tree_to_nodes(policy.sanitize(arch::parse_html(html)))
+}
+
 fn tree_to_nodes<D: Dom>(nodes: Vec<DomNode>) -> Vec<Node<D>> {
     nodes
         .into_iter()
@@ -86,6 +119,33 @@ fn tree_to_nodes<D: Dom>(nodes: Vec<DomNode>) -> Vec<Node<D>> {
         .collect()
 }
 
+/// Convert an HTML fragment to Rust source code that builds it.
+///
+/// This is to help migrate existing markup to Silkenweb. The code uses the
+/// typed element builders and attribute methods from [`silkenweb::elements`]
+/// where possible, and is preceded by the `use` declarations it needs. Text is
+/// added with [`ParentElement::text`], with whitespace collapsed outside of
+/// `<pre>` and `<textarea>` elements. The `html-to-silkenweb` binary in this
+/// crate converts an HTML file.
+///
+/// # Example
+///
+/// ```
+/// # use silkenweb_parse::html_to_rust;
+/// let code = html_to_rust(r#"<div class="card"><a href="/about">About   us</a></div>"#);
+///
+/// assert_eq!(
+///     code,
+///     r#"use silkenweb::{elements::html::*, prelude::*};
+///
+/// div().class("card").child(a().href("/about").text("About us"))
+/// "#
+/// );
+/// ```
+pub fn html_to_rust(html: &str) -> String {
+    rust_code::nodes_to_rust(&arch::parse_html(html))
+}
+
 /// Convert an HTML fragment to Silkenweb node expressions.
 ///
 /// This is for writing your own proc macros to parse HTML fragments at compile
@@ -95,57 +155,227 @@ fn tree_to_nodes<D: Dom>(nodes: Vec<DomNode>) -> Vec<Node<D>> {
 /// [`Node`][`silkenweb::node::Node`]. The [`Dom`][`silkenweb::dom::Dom`] type
 /// is left unspecified, so may need to be specified if it can't be determined
 /// with type inference.
+///
+/// Elements are built with the typed builders from [`silkenweb::elements`]
+/// where possible. See [`html_to_elements`] for details.
 pub fn html_to_tokens(dom_type: TokenStream, html: &str) -> Vec<TokenStream> {
-    tree_to_tokens(dom_type.into(), arch::parse_html(html))
-}
+    let dom_type: proc_macro2::TokenStream = dom_type.into();
+    let mut code_gen = CodeGen::new(dom_type.clone(), false);
 
-fn tree_to_tokens(dom_type: proc_macro2::TokenStream, nodes: Vec<DomNode>) -> Vec<TokenStream> {
-    nodes
+    code_gen
+        .nodes(arch::parse_html(html))
         .into_iter()
-        .filter_map(|src_node| match src_node {
-            DomNode::Element {
-                ns,
-                name,
-                mut attributes,
-                children,
-            } => {
-                // Sort attributes for testability
-                attributes.sort();
+        .map(|node| {
+            let node = match node {
+                NodeTokens::Element { element, .. } => element,
+                NodeTokens::Text(text) => quote!(::silkenweb::node::Text::<#dom_type>::new(#text)),
+            };
 
-                let children =
-                    tree_to_tokens(dom_type.clone(), children).into_iter().map(proc_macro2::TokenStream::from);
-                let attributes = attributes.into_iter().map(|(name, value)| {
-                    quote! { elem = elem.attribute(#name, #value); }
-                });
+            quote!(::silkenweb::node::Node::<#dom_type>::from(#node)).into()
+        })
+        .collect()
+}
 
-                let children = if children.len() == 0 {
-                    quote! {[] as [::silkenweb::node::Node<#dom_type>; 0]}
-                } else {
-                    quote! {[#(#children),*]}
-                };
+/// A typed element expression, from [`html_to_elements`].
+pub struct ElementTokens {
+    /// The type of the element, for example
+    /// `::silkenweb::elements::html::Div<D>`.
+    pub element_type: proc_macro2::TokenStream,
+    /// An expression that builds the element.
+    pub element: proc_macro2::TokenStream,
+    /// The `id` and [`ElementHandle`] type of each element with an `id`
+    /// attribute, in document order.
+    ///
+    /// [`ElementHandle`]: silkenweb::node::element::ElementHandle
+    pub ids: Vec<(String, proc_macro2::TokenStream)>,
+    /// An expression that builds the element and evaluates to `(element,
+    /// handles)`, where `handles` is a tuple with an [`ElementHandle`] for
+    /// each of [`ids`][Self::ids].
+    ///
+    /// [`ElementHandle`]: silkenweb::node::element::ElementHandle
+    pub element_with_ids: proc_macro2::TokenStream,
+}
 
-                Some(quote! {{
-                    use ::silkenweb::node::element::{Element, GenericElement, Namespace, ParentElement};
-                    let mut elem = GenericElement::<#dom_type>::new(
-                        &Namespace::Other(#ns.to_string()),
-                        #name
-                    );
+/// Convert an HTML fragment to typed Silkenweb element expressions.
+///
+/// This is like [`html_to_tokens`], but it only converts the top level
+/// elements, and provides their types. Known HTML and SVG elements are built
+/// with the typed builders from [`silkenweb::elements`], such as
+/// [`Div`][silkenweb::elements::html::Div], so they have typed event methods.
+/// Unknown elements, and void elements with children, are built with
+/// [`GenericElement`]. Attributes are set with the typed builder's method
+/// where there is one, and the value converts to its type without changing
+/// how it renders. Other attributes are set with [`Element::attribute`].
+///
+/// Handles to elements with an `id` attribute are available through
+/// [`ElementTokens::element_with_ids`], so statically authored fragments can
+/// be wired up with events.
+pub fn html_to_elements(dom_type: TokenStream, html: &str) -> Vec<ElementTokens> {
+    let dom_type: proc_macro2::TokenStream = dom_type.into();
 
-                    #(#attributes)*
+    arch::parse_html(html)
+        .into_iter()
+        .filter(|node| matches!(node, DomNode::Element { .. }))
+        .map(|node| {
+            let nodes = vec![node];
+            let mut code_gen = CodeGen::new(dom_type.clone(), false);
+            let mut code_gen_with_ids = CodeGen::new(dom_type.clone(), true);
+            let Some(NodeTokens::Element {
+                element_type,
+                element,
+            }) = code_gen.nodes(nodes.clone()).pop()
+            else {
+                unreachable!("Expected an element")
+            };
+            let Some(NodeTokens::Element {
+                element: element_with_ids,
+                ..
+            }) = code_gen_with_ids.nodes(nodes).pop()
+            else {
+                unreachable!("Expected an element")
+            };
+            let handles: Vec<_> = (0..code_gen_with_ids.ids.len()).map(handle_ident).collect();
 
-                    elem.children(#children)
-                }})
+            ElementTokens {
+                element_type,
+                element,
+                ids: code_gen_with_ids.ids,
+                element_with_ids: quote!({
+                    #(let mut #handles = ::std::option::Option::None;)*
+                    let element = #element_with_ids;
+                    (element, (#(#handles.unwrap(),)*))
+                }),
             }
-            DomNode::Text(text) => {
-                if text.trim().is_empty() {
-                    None
-                } else {
-                    Some(quote!(::silkenweb::node::Text::<#dom_type>::new(#text)))
+        })
+        .collect()
+}
+
+enum NodeTokens {
+    Element {
+        element_type: proc_macro2::TokenStream,
+        element: proc_macro2::TokenStream,
+    },
+    Text(String),
+}
+
+struct CodeGen {
+    dom_type: proc_macro2::TokenStream,
+    capture_ids: bool,
+    ids: Vec<(String, proc_macro2::TokenStream)>,
+}
+
+impl CodeGen {
+    fn new(dom_type: proc_macro2::TokenStream, capture_ids: bool) -> Self {
+        Self {
+            dom_type,
+            capture_ids,
+            ids: Vec::new(),
+        }
+    }
+
+    fn nodes(&mut self, nodes: Vec<DomNode>) -> Vec<NodeTokens> {
+        nodes
+            .into_iter()
+            .filter_map(|src_node| match src_node {
+                DomNode::Element {
+                    ns,
+                    name,
+                    mut attributes,
+                    children,
+                } => {
+                    // Sort attributes for testability
+                    attributes.sort();
+                    Some(self.element(&ns, &name, attributes, children))
+                }
+                DomNode::Text(text) => {
+                    if text.trim().is_empty() {
+                        None
+                    } else {
+                        Some(NodeTokens::Text(text))
+                    }
                 }
+            })
+            .collect()
+    }
+
+    fn element(
+        &mut self,
+        ns: &str,
+        name: &str,
+        attributes: Vec<(String, String)>,
+        children: Vec<DomNode>,
+    ) -> NodeTokens {
+        let dom_type = self.dom_type.clone();
+        let has_children = children.iter().any(|child| match child {
+            DomNode::Element { .. } => true,
+            DomNode::Text(text) => !text.trim().is_empty(),
+        });
+        let typed = TypedElement::find(ns, name).filter(|typed| typed.is_parent() || !has_children);
+        let (element_type, constructor) = match &typed {
+            Some(typed) => (typed.element_type(&dom_type), typed.constructor(&dom_type)),
+            None => (
+                quote!(::silkenweb::node::element::GenericElement<#dom_type>),
+                quote!(::silkenweb::node::element::GenericElement::<#dom_type>::new(
+                    &::silkenweb::node::element::Namespace::Other(#ns.to_string()),
+                    #name
+                )),
+            ),
+        };
+
+        let id = attributes
+            .iter()
+            .find(|(name, _)| name == "id")
+            .filter(|_| self.capture_ids)
+            .map(|(_, id)| id.clone());
+        let handle = id.map(|id| {
+            let handle = handle_ident(self.ids.len());
+            self.ids.push((
+                id,
+                quote!(::silkenweb::node::element::ElementHandle<
+                    #dom_type,
+                    <#element_type as ::silkenweb::node::element::Element>::DomElement
+                >),
+            ));
+            handle
+        });
+        let attributes = attributes.iter().map(|(name, value)| {
+            match typed
+                .as_ref()
+                .and_then(|typed| typed.attribute(name, value))
+            {
+                Some((method, value)) => quote!(.#method(#value)),
+                None => quote!(.attribute(#name, #value)),
             }
-        })
-        .map(|node| quote!(::silkenweb::node::Node::<#dom_type>::from(#node)).into())
-        .collect::<Vec<_>>()
+        });
+        let children = self.nodes(children).into_iter().map(|child| match child {
+            NodeTokens::Element { element, .. } => quote!(.child(#element)),
+            NodeTokens::Text(text) => quote!(.text(#text)),
+        });
+        let mut element = quote!(#constructor #(#attributes)* #(#children)*);
+
+        if let Some(handle) = handle {
+            element = quote!({
+                let element = #element;
+                #handle = ::std::option::Option::Some(
+                    ::silkenweb::node::element::Element::handle(&element)
+                );
+                element
+            });
+        }
+
+        NodeTokens::Element {
+            element_type,
+            element: quote!({
+                #[allow(unused_imports)]
+                use ::silkenweb::node::element::{Element as _, ParentElement as _};
+                #element
+            }),
+        }
+    }
+}
+
+fn handle_ident(index: usize) -> proc_macro2::Ident {
+    format_ident!("handle{index}")
 }
 
 #[cfg_browser(false)]
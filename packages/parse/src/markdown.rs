@@ -0,0 +1,571 @@
+//! Render markdown to Silkenweb nodes.
+//!
+//! Markdown is parsed with [`pulldown_cmark`], and rendered directly to
+//! [`Node`]s, without going through an HTML string. Override methods on
+//! [`Render`] to change how specific elements are rendered, for example to
+//! highlight code blocks, or render links with [`router::anchor`].
+//!
+//! Raw HTML in the markdown is sanitized with a [`Policy`]. Inline HTML
+//! elements can contain text and code spans, such as `<kbd>Ctrl</kbd>`, but
+//! other markdown inside them, such as `<b>*text*</b>`, ends the element, so it
+//! renders as `<b></b><em>text</em>`.
+//!
+//! Link and image URLs must be allowed by the [`Policy`], whatever the
+//! [`Render`] implementation. Links to other URLs are rendered as just their
+//! content, and images as just their description, so `[text](javascript:..)`
+//! renders as `text`.
+//!
+//! This module requires the `markdown` feature.
+//!
+//! # Example
+//!
+//! ```
+//! # use silkenweb::{dom::Dom, node::Node, prelude::*, router};
+//! # use silkenweb_parse::markdown::{markdown_to_nodes, Link, Markdown, Render};
+//! let nodes: Vec<Node> = markdown_to_nodes("Some *emphasized* text");
+//! assert_eq!(nodes[0].to_string(), "<p>Some <em>emphasized</em> text</p>");
+//!
+//! struct Docs;
+//!
+//! impl<D: Dom> Render<D> for Docs {
+//!     fn link(&self, link: Link, children: Vec<Node<D>>) -> Node<D> {
+//!         router::anchor(link.dest).children(children).into()
+//!     }
+//! }
+//!
+//! let nodes: Vec<Node> = Markdown::new().renderer(Docs).to_nodes("[Home](/)");
+//! ```
+//!
+//! [`router::anchor`]: silkenweb::router::anchor
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Parser, Tag};
+pub use pulldown_cmark::{LinkType, Options};
+use silkenweb::{
+    dom::Dom,
+    elements::{
+        html::{
+            a, blockquote, br, code, del, div, em, h1, h2, h3, h4, h5, h6, hr, img, input, li, ol,
+            p, pre, strong, sup, table, tbody, td, th, thead, tr, ul,
+        },
+        HtmlElement,
+    },
+    node::{
+        element::{Element, ParentElement},
+        Node, Text,
+    },
+};
+
+use crate::{html_to_sanitized_nodes, sanitize::Policy};
+
+/// Render markdown to nodes, with the default options and renderer.
+pub fn markdown_to_nodes<D: Dom>(markdown: &str) -> Vec<Node<D>> {
+    Markdown::new().to_nodes(markdown)
+}
+
+/// Markdown rendering options.
+pub struct Markdown<R = DefaultRender> {
+    options: Options,
+    html_policy: Policy,
+    renderer: R,
+}
+
+impl Markdown {
+    /// Tables, footnotes, strikethrough and task lists are enabled, and raw
+    /// HTML is sanitized with [`Policy::default`].
+    pub fn new() -> Self {
+        Self {
+            options: Options::ENABLE_TABLES
+                | Options::ENABLE_FOOTNOTES
+                | Options::ENABLE_STRIKETHROUGH
+                | Options::ENABLE_TASKLISTS,
+            html_policy: Policy::default(),
+            renderer: DefaultRender,
+        }
+    }
+}
+
+impl Default for Markdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> Markdown<R> {
+    /// Set the markdown extensions.
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the policy used to sanitize raw HTML.
+    pub fn html_policy(mut self, policy: Policy) -> Self {
+        self.html_policy = policy;
+        self
+    }
+
+    /// Use `renderer` to override how elements are rendered.
+    pub fn renderer<R2>(self, renderer: R2) -> Markdown<R2> {
+        Markdown {
+            options: self.options,
+            html_policy: self.html_policy,
+            renderer,
+        }
+    }
+
+    /// Render `markdown` to nodes.
+    pub fn to_nodes<D: Dom>(&self, markdown: &str) -> Vec<Node<D>>
+    where
+        R: Render<D>,
+    {
+        let mut builder = Builder {
+            markdown: self,
+            stack: Vec::new(),
+            nodes: Vec::new(),
+            html: String::new(),
+        };
+
+        for event in Parser::new_ext(markdown, self.options) {
+            builder.event(event);
+        }
+
+        builder.flush_html();
+        builder.nodes
+    }
+}
+
+/// A markdown link.
+pub struct Link<'a> {
+    pub link_type: LinkType,
+    pub dest: &'a str,
+    pub title: &'a str,
+}
+
+/// A markdown image.
+pub struct Image<'a> {
+    pub link_type: LinkType,
+    pub dest: &'a str,
+    pub title: &'a str,
+    /// The image description, as plain text.
+    pub alt: &'a str,
+}
+
+/// Render markdown elements.
+///
+/// Each method has a default implementation, so implementors only need to
+/// override the elements they want to customize.
+pub trait Render<D: Dom> {
+    /// Render a fenced or indented code block.
+    ///
+    /// `language` is the first word of the info string of a fenced code block.
+    fn code_block(&self, language: Option<&str>, code_text: &str) -> Node<D> {
+        let code_elem = code().text(code_text);
+        let code_elem = match language {
+            Some(language) => code_elem.class(format!("language-{language}")),
+            None => code_elem,
+        };
+
+        pre().child(code_elem).into()
+    }
+
+    /// Render a link, given its rendered content.
+    fn link(&self, link: Link, children: Vec<Node<D>>) -> Node<D> {
+        let elem = a().href(link.dest).children(children);
+
+        if link.title.is_empty() {
+            elem.into()
+        } else {
+            elem.title(link.title).into()
+        }
+    }
+
+    /// Render an image.
+    fn image(&self, image: Image) -> Node<D> {
+        let elem = img().src(image.dest).alt(image.alt);
+
+        if image.title.is_empty() {
+            elem.into()
+        } else {
+            elem.title(image.title).into()
+        }
+    }
+
+    /// Render a heading, given its rendered content.
+    ///
+    /// `level` is from 1 to 6.
+    fn heading(
+        &self,
+        level: u8,
+        id: Option<&str>,
+        classes: &[&str],
+        children: Vec<Node<D>>,
+    ) -> Node<D> {
+        fn heading<D: Dom, E>(
+            elem: E,
+            id: Option<&str>,
+            classes: &[&str],
+            children: Vec<Node<D>>,
+        ) -> Node<D>
+        where
+            E: HtmlElement + ParentElement<D> + Into<Node<D>>,
+        {
+            let elem = elem.classes(classes).children(children);
+
+            match id {
+                Some(id) => elem.id(id).into(),
+                None => elem.into(),
+            }
+        }
+
+        match level {
+            1 => heading(h1(), id, classes, children),
+            2 => heading(h2(), id, classes, children),
+            3 => heading(h3(), id, classes, children),
+            4 => heading(h4(), id, classes, children),
+            5 => heading(h5(), id, classes, children),
+            _ => heading(h6(), id, classes, children),
+        }
+    }
+}
+
+/// Render all elements with the default implementations from [`Render`].
+pub struct DefaultRender;
+
+impl<D: Dom> Render<D> for DefaultRender {}
+
+struct Builder<'a, 'md, R, D: Dom> {
+    markdown: &'a Markdown<R>,
+    stack: Vec<Frame<'md, D>>,
+    nodes: Vec<Node<D>>,
+    html: String,
+}
+
+enum Frame<'md, D: Dom> {
+    Element {
+        tag: Tag<'md>,
+        children: Vec<Node<D>>,
+    },
+    /// Code blocks and images only contain text.
+    Text { tag: Tag<'md>, text: String },
+    /// A tag inside a text frame, such as emphasis in an image description.
+    Ignored,
+}
+
+impl<'a, 'md, R: Render<D>, D: Dom> Builder<'a, 'md, R, D> {
+    fn event(&mut self, event: Event<'md>) {
+        if self.buffer_inline_html(&event) {
+            return;
+        }
+
+        if !matches!(event, Event::Html(_)) {
+            self.flush_html();
+        }
+
+        match event {
+            Event::Start(tag) => {
+                let frame = if self.text_frame().is_some() {
+                    Frame::Ignored
+                } else {
+                    match tag {
+                        Tag::CodeBlock(_) | Tag::Image(..) => Frame::Text {
+                            tag,
+                            text: String::new(),
+                        },
+                        tag => Frame::Element {
+                            tag,
+                            children: Vec::new(),
+                        },
+                    }
+                };
+
+                self.stack.push(frame);
+            }
+            Event::End(_) => {
+                let node = match self.stack.pop().expect("Unbalanced markdown tags") {
+                    Frame::Element {
+                        tag: Tag::Link(_, dest, _),
+                        children,
+                    } if !self.is_url_allowed(&dest) => {
+                        for child in children {
+                            self.push(child);
+                        }
+
+                        return;
+                    }
+                    Frame::Text {
+                        tag: Tag::Image(_, dest, _),
+                        text,
+                    } if !self.is_url_allowed(&dest) => Text::new(&text).into(),
+                    Frame::Element { tag, children } => self.element(tag, children),
+                    Frame::Text { tag, text } => self.text_element(tag, &text),
+                    Frame::Ignored => return,
+                };
+                self.push(node);
+            }
+            Event::Text(text) => {
+                if let Some(content) = self.text_frame() {
+                    content.push_str(&text);
+                } else {
+                    self.push(Text::new(&text).into());
+                }
+            }
+            Event::Code(text) => {
+                if let Some(content) = self.text_frame() {
+                    content.push_str(&text);
+                } else {
+                    self.push(code().text(text.as_ref()).into());
+                }
+            }
+            Event::Html(html) => self.html.push_str(&html),
+            Event::FootnoteReference(name) => self.push(
+                sup()
+                    .class("footnote-reference")
+                    .child(a().href(format!("#{name}")).text(name.as_ref()))
+                    .into(),
+            ),
+            Event::SoftBreak => self.push(Text::new("\n").into()),
+            Event::HardBreak => self.push(br().into()),
+            Event::Rule => self.push(hr().into()),
+            Event::TaskListMarker(checked) => self.push(
+                input()
+                    .r#type("checkbox")
+                    .disabled(true)
+                    .checked(checked)
+                    .into(),
+            ),
+        }
+    }
+
+    /// The text of the innermost text frame, if we're in one.
+    fn text_frame(&mut self) -> Option<&mut String> {
+        self.stack.iter_mut().rev().find_map(|frame| match frame {
+            Frame::Element { .. } => None,
+            Frame::Text { text, .. } => Some(text),
+            Frame::Ignored => None,
+        })
+    }
+
+    fn push(&mut self, node: Node<D>) {
+        match self.stack.last_mut() {
+            Some(Frame::Element { children, .. }) => children.push(node),
+            Some(Frame::Text { .. } | Frame::Ignored) => (),
+            None => self.nodes.push(node),
+        }
+    }
+
+    /// Add text to the HTML buffer if it's inside an unclosed inline HTML
+    /// element.
+    ///
+    /// Inline HTML is split into separate events, so `<kbd>Ctrl</kbd>` is
+    /// `Html("<kbd>")`, `Text("Ctrl")`, `Html("</kbd>")`. Buffering the text
+    /// means the HTML can be parsed in one go.
+    fn buffer_inline_html(&mut self, event: &Event) -> bool {
+        if self.html.is_empty() || !has_open_elements(&self.html) {
+            return false;
+        }
+
+        match event {
+            Event::Text(text) => self.html.push_str(&escape(text)),
+            Event::Code(text) => {
+                self.html.push_str("<code>");
+                self.html.push_str(&escape(text));
+                self.html.push_str("</code>");
+            }
+            Event::SoftBreak => self.html.push('\n'),
+            Event::HardBreak => self.html.push_str("<br>"),
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn flush_html(&mut self) {
+        if !self.html.is_empty() {
+            let html = std::mem::take(&mut self.html);
+
+            for node in html_to_sanitized_nodes(&html, &self.markdown.html_policy) {
+                self.push(node);
+            }
+        }
+    }
+
+    fn is_url_allowed(&self, url: &str) -> bool {
+        self.markdown.html_policy.is_url_allowed(url)
+    }
+
+    fn element(&self, tag: Tag<'md>, children: Vec<Node<D>>) -> Node<D> {
+        let renderer = &self.markdown.renderer;
+
+        match tag {
+            Tag::Paragraph => p().children(children).into(),
+            Tag::Heading(level, id, classes) => {
+                renderer.heading(heading_level(level), id, &classes, children)
+            }
+            Tag::BlockQuote => blockquote().children(children).into(),
+            Tag::List(Some(1)) => ol().children(children).into(),
+            Tag::List(Some(start)) => match u32::try_from(start) {
+                Ok(start) => ol().start(start),
+                // Too big for the typed attribute, so set it as text.
+                Err(_) => ol().attribute("start", start.to_string()),
+            }
+            .children(children)
+            .into(),
+            Tag::List(None) => ul().children(children).into(),
+            Tag::Item => li().children(children).into(),
+            Tag::FootnoteDefinition(name) => div()
+                .class("footnote-definition")
+                .id(name.as_ref())
+                .child(sup().class("footnote-definition-label").text(name.as_ref()))
+                .children(children)
+                .into(),
+            Tag::Table(_) => {
+                let mut children = children.into_iter();
+                let head = children.next();
+
+                table()
+                    .children(head)
+                    .child(tbody().children(children))
+                    .into()
+            }
+            Tag::TableHead => thead().child(tr().children(children)).into(),
+            Tag::TableRow => tr().children(children).into(),
+            Tag::TableCell => {
+                let alignment = self.column_alignment();
+                let in_head = self.stack.iter().any(|frame| {
+                    matches!(
+                        frame,
+                        Frame::Element {
+                            tag: Tag::TableHead,
+                            ..
+                        }
+                    )
+                });
+                if in_head {
+                    aligned(th(), alignment).children(children).into()
+                } else {
+                    aligned(td(), alignment).children(children).into()
+                }
+            }
+            Tag::Emphasis => em().children(children).into(),
+            Tag::Strong => strong().children(children).into(),
+            Tag::Strikethrough => del().children(children).into(),
+            Tag::Link(link_type, dest, title) => renderer.link(
+                Link {
+                    link_type,
+                    dest: &dest,
+                    title: &title,
+                },
+                children,
+            ),
+            Tag::CodeBlock(_) | Tag::Image(..) => {
+                unreachable!("Code blocks and images are text frames")
+            }
+        }
+    }
+
+    fn text_element(&self, tag: Tag<'md>, text: &str) -> Node<D> {
+        let renderer = &self.markdown.renderer;
+
+        match tag {
+            Tag::CodeBlock(CodeBlockKind::Fenced(info)) => {
+                renderer.code_block(info.split_whitespace().next(), text)
+            }
+            Tag::CodeBlock(CodeBlockKind::Indented) => renderer.code_block(None, text),
+            Tag::Image(link_type, dest, title) => renderer.image(Image {
+                link_type,
+                dest: &dest,
+                title: &title,
+                alt: text,
+            }),
+            _ => unreachable!("Only code blocks and images are text frames"),
+        }
+    }
+
+    /// The alignment of the table cell that is being built.
+    fn column_alignment(&self) -> Alignment {
+        let mut frames = self.stack.iter().rev();
+        let column = match frames.next() {
+            Some(Frame::Element { children, .. }) => children.len(),
+            _ => 0,
+        };
+
+        frames
+            .find_map(|frame| match frame {
+                Frame::Element {
+                    tag: Tag::Table(alignments),
+                    ..
+                } => Some(alignments.get(column).copied().unwrap_or(Alignment::None)),
+                _ => None,
+            })
+            .unwrap_or(Alignment::None)
+    }
+}
+
+fn aligned<E: HtmlElement>(elem: E, alignment: Alignment) -> E {
+    let align = match alignment {
+        Alignment::None => return elem,
+        Alignment::Left => "left",
+        Alignment::Center => "center",
+        Alignment::Right => "right",
+    };
+
+    elem.style_property("text-align", align)
+}
+
+/// Does `html` have elements that haven't been closed yet?
+///
+/// This is a simple scan of the tags, which is enough for the inline HTML that
+/// `pulldown_cmark` splits up, as text is escaped before it's buffered.
+fn has_open_elements(html: &str) -> bool {
+    const VOID_ELEMENTS: &[&str] = &[
+        "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source",
+        "track", "wbr",
+    ];
+
+    let mut depth = 0usize;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let end = rest.find('>').unwrap_or(rest.len());
+        let tag = &rest[..end];
+        rest = &rest[end..];
+
+        if tag.starts_with('/') {
+            depth = depth.saturating_sub(1);
+        } else if !tag.ends_with('/') && tag.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let name = tag
+                .split(|c: char| c.is_ascii_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+
+            if !VOID_ELEMENTS.contains(&name.as_str()) {
+                depth += 1;
+            }
+        }
+    }
+
+    depth > 0
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
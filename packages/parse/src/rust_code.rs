@@ -0,0 +1,218 @@
+//! Generate Rust source code that builds an HTML fragment.
+use std::fmt::Write;
+
+use crate::{
+    elements::{TypedElement, HTML_NAMESPACE, SVG_NAMESPACE},
+    DomNode,
+};
+
+const MAX_WIDTH: usize = 100;
+const INDENT: usize = 4;
+
+pub fn nodes_to_rust(nodes: &[DomNode]) -> String {
+    let mut code_gen = CodeGen::default();
+    let exprs: Vec<String> = nodes
+        .iter()
+        .filter_map(|node| code_gen.node(node, 0, false))
+        .collect();
+    let mut source = String::new();
+
+    if code_gen.uses_generic_element {
+        source.push_str("use silkenweb::{\n");
+        source.push_str("    elements::html::*,\n");
+        source.push_str("    node::element::{GenericElement, Namespace},\n");
+        source.push_str("    prelude::*,\n");
+        source.push_str("};\n");
+    } else {
+        source.push_str("use silkenweb::{elements::html::*, prelude::*};\n");
+    }
+
+    for expr in exprs {
+        writeln!(source, "\n{expr}").unwrap();
+    }
+
+    source
+}
+
+#[derive(Default)]
+struct CodeGen {
+    uses_generic_element: bool,
+}
+
+enum Method {
+    Class(Vec<String>),
+    Attribute(String, String),
+    TypedAttribute(String, String),
+    Text(String),
+    Child(String),
+}
+
+impl CodeGen {
+    /// Generate an expression for `node`, indented by `indent`.
+    ///
+    /// Only the 2nd and subsequent lines are indented, as the first line is
+    /// positioned by the caller.
+    fn node(&mut self, node: &DomNode, indent: usize, preserve_space: bool) -> Option<String> {
+        match node {
+            DomNode::Element {
+                ns,
+                name,
+                attributes,
+                children,
+            } => Some(self.element(ns, name, attributes, children, indent, preserve_space)),
+            DomNode::Text(text) => {
+                text_content(text, preserve_space).map(|text| format!("{text:?}"))
+            }
+        }
+    }
+
+    fn element(
+        &mut self,
+        ns: &str,
+        name: &str,
+        attributes: &[(String, String)],
+        children: &[DomNode],
+        indent: usize,
+        preserve_space: bool,
+    ) -> String {
+        let preserve_space = preserve_space || matches!(name, "pre" | "textarea");
+        let has_children = children.iter().any(|child| match child {
+            DomNode::Element { .. } => true,
+            DomNode::Text(text) => text_content(text, preserve_space).is_some(),
+        });
+        let typed = TypedElement::find(ns, name).filter(|typed| typed.is_parent() || !has_children);
+        let constructor = match &typed {
+            Some(typed) => {
+                if typed.is_svg() {
+                    format!("svg::{}()", typed.snake_name())
+                } else {
+                    format!("{}()", typed.snake_name())
+                }
+            }
+            None => {
+                self.uses_generic_element = true;
+                let namespace = match ns {
+                    HTML_NAMESPACE => "Namespace::Html".to_string(),
+                    SVG_NAMESPACE => "Namespace::Svg".to_string(),
+                    ns => format!("Namespace::Other({ns:?}.to_string())"),
+                };
+
+                format!("GenericElement::new(&{namespace}, {name:?})")
+            }
+        };
+
+        let child_indent = indent + INDENT;
+        let mut methods = Vec::new();
+
+        for (name, value) in attributes {
+            if name == "class" {
+                methods.push(Method::Class(
+                    value.split_whitespace().map(str::to_string).collect(),
+                ));
+            } else if let Some((method, value)) = typed
+                .as_ref()
+                .and_then(|typed| typed.attribute(name, value))
+            {
+                methods.push(Method::TypedAttribute(
+                    method.to_string(),
+                    value.to_string(),
+                ));
+            } else {
+                methods.push(Method::Attribute(name.clone(), value.clone()));
+            }
+        }
+
+        for child in children {
+            match child {
+                DomNode::Element { .. } => {
+                    if let Some(child) = self.node(child, child_indent, preserve_space) {
+                        methods.push(Method::Child(child));
+                    }
+                }
+                DomNode::Text(text) => {
+                    if let Some(text) = text_content(text, preserve_space) {
+                        methods.push(Method::Text(text));
+                    }
+                }
+            }
+        }
+
+        let methods: Vec<String> = methods
+            .into_iter()
+            .map(|method| match method {
+                Method::Class(classes) if classes.len() == 1 => {
+                    format!(".class({:?})", classes[0])
+                }
+                Method::Class(classes) => format!(".classes({classes:?})"),
+                Method::Attribute(name, value) => format!(".attribute({name:?}, {value:?})"),
+                Method::TypedAttribute(method, value) => format!(".{method}({value})"),
+                Method::Text(text) => format!(".text({text:?})"),
+                Method::Child(child) => {
+                    if child.contains('\n') {
+                        let inner = " ".repeat(child_indent + INDENT);
+                        let outer = " ".repeat(child_indent);
+                        format!(".child(\n{inner}{}\n{outer})", indent_lines(&child, INDENT))
+                    } else {
+                        format!(".child({child})")
+                    }
+                }
+            })
+            .collect();
+
+        let single_line = format!("{constructor}{}", methods.concat());
+
+        if !single_line.contains('\n') && indent + single_line.len() <= MAX_WIDTH {
+            return single_line;
+        }
+
+        let mut expr = constructor;
+
+        for method in methods {
+            write!(expr, "\n{}{method}", " ".repeat(child_indent)).unwrap();
+        }
+
+        expr
+    }
+}
+
+/// The text content of a text node, or `None` if it's empty.
+///
+/// Whitespace is collapsed unless `preserve_space` is set.
+fn text_content(text: &str, preserve_space: bool) -> Option<String> {
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    if preserve_space {
+        return Some(text.to_string());
+    }
+
+    let mut collapsed = String::new();
+    let mut in_space = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            in_space = true;
+        } else {
+            if in_space {
+                collapsed.push(' ');
+                in_space = false;
+            }
+
+            collapsed.push(c);
+        }
+    }
+
+    if in_space {
+        collapsed.push(' ');
+    }
+
+    Some(collapsed)
+}
+
+/// Indent all but the first line of `text`.
+fn indent_lines(text: &str, indent: usize) -> String {
+    text.lines()
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}", " ".repeat(indent)))
+}
@@ -0,0 +1,360 @@
+//! Sanitize untrusted HTML.
+//!
+//! A [`Policy`] is an allowlist of elements, attributes and URL schemes. Use
+//! it with [`html_to_sanitized_nodes`] to insert untrusted HTML, such as user
+//! supplied markup. Sanitization is applied to the parsed tree, so the output
+//! is the same in the browser and on the server, given the same parse tree.
+//!
+//! - Elements that aren't allowed are removed, but their children are kept.
+//! - Elements whose content should never be rendered, such as `<script>` and
+//!   `<style>`, are removed along with their children, even if they're
+//!   allowed. [`Policy::allow_removed_content`] opts out of this.
+//! - Elements that aren't in the HTML namespace, such as SVG or MathML, are
+//!   removed along with their children.
+//! - Attributes are removed unless they're allowed for all elements or the
+//!   specific element. Event handler attributes (starting with `on`) are always
+//!   removed.
+//! - URL attributes, such as `href` and `src`, are removed unless the URL is
+//!   relative or has an allowed scheme. For attributes with a list of URLs,
+//!   such as `srcset` and `ping`, every URL must be allowed.
+//! - Element and attribute names in the policy are case insensitive.
+//!
+//! # Example
+//!
+//! ```
+//! # use silkenweb::node::Node;
+//! # use silkenweb_parse::{html_to_sanitized_nodes, sanitize::Policy};
+//! let nodes: Vec<Node> = html_to_sanitized_nodes(
+//!     r#"<p onclick="steal()"><a href="javascript:steal()">Link</a><script>steal()</script></p>"#,
+//!     &Policy::default(),
+//! );
+//!
+//! assert_eq!(nodes[0].to_string(), "<p><a>Link</a></p>");
+//! ```
+//!
+//! [`html_to_sanitized_nodes`]: crate::html_to_sanitized_nodes
+use std::collections::{HashMap, HashSet};
+
+use crate::{elements::HTML_NAMESPACE, DomNode};
+
+/// An allowlist of elements, attributes and URL schemes.
+///
+/// [`Policy::default`] is a safe policy for formatted text, such as rendered
+/// markdown. [`Policy::empty`] only allows text, and can be extended with the
+/// `allow_*` methods.
+#[derive(Clone, Debug)]
+pub struct Policy {
+    elements: HashSet<String>,
+    removed_content: HashSet<String>,
+    attributes: HashSet<String>,
+    element_attributes: HashMap<String, HashSet<String>>,
+    url_attributes: HashSet<String>,
+    url_schemes: HashSet<String>,
+    relative_urls: bool,
+}
+
+impl Policy {
+    /// A policy that only allows text.
+    pub fn empty() -> Self {
+        Self {
+            elements: HashSet::new(),
+            removed_content: strings([
+                "iframe",
+                "noembed",
+                "noframes",
+                "noscript",
+                "object",
+                "embed",
+                "plaintext",
+                "script",
+                "style",
+                "template",
+                "textarea",
+                "title",
+                "xmp",
+            ]),
+            attributes: HashSet::new(),
+            element_attributes: HashMap::new(),
+            url_attributes: strings([
+                "action",
+                "background",
+                "cite",
+                "formaction",
+                "href",
+                "longdesc",
+                "ping",
+                "poster",
+                "src",
+                "srcset",
+                "xlink:href",
+            ]),
+            url_schemes: HashSet::new(),
+            relative_urls: true,
+        }
+    }
+
+    /// Allow `elements`.
+    ///
+    /// This doesn't apply to elements that are removed along with their
+    /// content, such as `<script>` and `<style>`. See
+    /// [`allow_removed_content`][Self::allow_removed_content].
+    pub fn allow_elements<S: Into<String>>(
+        mut self,
+        elements: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.elements.extend(lowercase(elements));
+        self
+    }
+
+    /// Stop removing `elements` along with their content.
+    ///
+    /// By default, elements such as `<script>`, `<style>`, `<iframe>` and
+    /// `<textarea>` are removed along with their content, even if they're
+    /// allowed. After calling this, they're treated like any other element:
+    /// they're kept if they're allowed, otherwise they're removed but their
+    /// content is kept as text.
+    ///
+    /// **This is dangerous.** Allowing `<script>` or `<style>` lets untrusted
+    /// HTML run code or restyle the whole page, so only use this for markup you
+    /// trust.
+    pub fn allow_removed_content<S: Into<String>>(
+        mut self,
+        elements: impl IntoIterator<Item = S>,
+    ) -> Self {
+        for element in lowercase(elements) {
+            self.removed_content.remove(&element);
+        }
+
+        self
+    }
+
+    /// Allow `attributes` on all allowed elements.
+    pub fn allow_attributes<S: Into<String>>(
+        mut self,
+        attributes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.attributes.extend(lowercase(attributes));
+        self
+    }
+
+    /// Allow `attributes` on `element`.
+    pub fn allow_element_attributes<S: Into<String>>(
+        mut self,
+        element: &str,
+        attributes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.element_attributes
+            .entry(element.to_ascii_lowercase())
+            .or_default()
+            .extend(lowercase(attributes));
+        self
+    }
+
+    /// Allow URLs with any of `schemes`, such as `"https"`.
+    pub fn allow_url_schemes<S: Into<String>>(
+        mut self,
+        schemes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.url_schemes.extend(lowercase(schemes));
+        self
+    }
+
+    /// Allow relative URLs, without a scheme. They're allowed by default.
+    pub fn allow_relative_urls(mut self, allow: bool) -> Self {
+        self.relative_urls = allow;
+        self
+    }
+
+    pub(crate) fn sanitize(&self, nodes: Vec<DomNode>) -> Vec<DomNode> {
+        let mut sanitized = Vec::new();
+
+        for node in nodes {
+            match node {
+                DomNode::Element {
+                    ns,
+                    name,
+                    attributes,
+                    children,
+                } => {
+                    if ns != HTML_NAMESPACE || self.removed_content.contains(&name) {
+                        continue;
+                    }
+
+                    let children = self.sanitize(children);
+
+                    if self.elements.contains(&name) {
+                        let attributes = attributes
+                            .into_iter()
+                            .filter(|(attr, value)| self.is_attribute_allowed(&name, attr, value))
+                            .collect();
+
+                        sanitized.push(DomNode::Element {
+                            ns,
+                            name,
+                            attributes,
+                            children,
+                        });
+                    } else {
+                        sanitized.extend(children);
+                    }
+                }
+                DomNode::Text(text) => sanitized.push(DomNode::Text(text)),
+            }
+        }
+
+        sanitized
+    }
+
+    fn is_attribute_allowed(&self, element: &str, attribute: &str, value: &str) -> bool {
+        let attribute = attribute.to_ascii_lowercase();
+
+        if attribute.starts_with("on") {
+            return false;
+        }
+
+        let allowed = self.attributes.contains(&attribute)
+            || self
+                .element_attributes
+                .get(element)
+                .is_some_and(|attributes| attributes.contains(&attribute));
+
+        allowed
+            && (!self.url_attributes.contains(&attribute)
+                || self.is_url_list_allowed(&attribute, value))
+    }
+
+    fn is_url_list_allowed(&self, attribute: &str, value: &str) -> bool {
+        match attribute {
+            // Each candidate is a URL, optionally followed by a descriptor.
+            // Splitting URLs that contain commas is conservative, as each
+            // part still has to be allowed.
+            "srcset" => value.split(',').all(|candidate| {
+                self.is_url_allowed(candidate.split_whitespace().next().unwrap_or(""))
+            }),
+            "ping" => value.split_whitespace().all(|url| self.is_url_allowed(url)),
+            _ => self.is_url_allowed(value),
+        }
+    }
+
+    /// Is `url` relative, or does it have an allowed scheme?
+    pub(crate) fn is_url_allowed(&self, url: &str) -> bool {
+        // Browsers ignore leading and trailing control characters and spaces,
+        // and tabs and newlines anywhere in a URL.
+        let url: String = url
+            .trim_matches(|c: char| c <= ' ')
+            .chars()
+            .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+            .collect();
+
+        match url.find([':', '/', '?', '#']) {
+            Some(index) if url[index..].starts_with(':') => self
+                .url_schemes
+                .contains(&url[..index].to_ascii_lowercase()),
+            _ => self.relative_urls,
+        }
+    }
+}
+
+impl Default for Policy {
+    /// A safe policy for formatted text.
+    ///
+    /// It allows common text, list and table elements, links and images. URLs
+    /// must be relative, or use the `http`, `https` or `mailto` schemes.
+    fn default() -> Self {
+        Self::empty()
+            .allow_elements([
+                "a",
+                "abbr",
+                "b",
+                "bdi",
+                "bdo",
+                "blockquote",
+                "br",
+                "caption",
+                "cite",
+                "code",
+                "col",
+                "colgroup",
+                "data",
+                "dd",
+                "del",
+                "details",
+                "dfn",
+                "div",
+                "dl",
+                "dt",
+                "em",
+                "figcaption",
+                "figure",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "hr",
+                "i",
+                "img",
+                "ins",
+                "kbd",
+                "li",
+                "mark",
+                "ol",
+                "p",
+                "pre",
+                "q",
+                "rp",
+                "rt",
+                "ruby",
+                "s",
+                "samp",
+                "small",
+                "span",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "table",
+                "tbody",
+                "td",
+                "tfoot",
+                "th",
+                "thead",
+                "time",
+                "tr",
+                "u",
+                "ul",
+                "var",
+                "wbr",
+            ])
+            .allow_attributes(["dir", "lang", "title"])
+            .allow_element_attributes("a", ["href", "hreflang"])
+            .allow_element_attributes("img", ["alt", "height", "src", "width"])
+            .allow_element_attributes("blockquote", ["cite"])
+            .allow_element_attributes("q", ["cite"])
+            .allow_element_attributes("del", ["cite", "datetime"])
+            .allow_element_attributes("ins", ["cite", "datetime"])
+            .allow_element_attributes("col", ["span"])
+            .allow_element_attributes("colgroup", ["span"])
+            .allow_element_attributes("td", ["colspan", "headers", "rowspan"])
+            .allow_element_attributes("th", ["abbr", "colspan", "headers", "rowspan", "scope"])
+            .allow_element_attributes("ol", ["reversed", "start", "type"])
+            .allow_element_attributes("li", ["value"])
+            .allow_element_attributes("data", ["value"])
+            .allow_element_attributes("time", ["datetime"])
+            .allow_element_attributes("details", ["open"])
+            .allow_url_schemes(["http", "https", "mailto"])
+    }
+}
+
+fn strings<const N: usize>(strings: [&str; N]) -> HashSet<String> {
+    strings.into_iter().map(str::to_string).collect()
+}
+
+fn lowercase<S: Into<String>>(
+    strings: impl IntoIterator<Item = S>,
+) -> impl Iterator<Item = String> {
+    strings
+        .into_iter()
+        .map(|string| string.into().to_ascii_lowercase())
+}
@@ -0,0 +1,240 @@
+#![cfg(feature = "markdown")]
+use silkenweb::{
+    dom::{Dom, Dry},
+    elements::html::{div, span},
+    node::{
+        element::{Element, ParentElement},
+        Node,
+    },
+};
+use silkenweb_parse::{
+    markdown::{Image, Link, Markdown, Options, Render},
+    sanitize::Policy,
+};
+
+fn render<R: Render<Dry>>(markdown: &Markdown<R>, text: &str) -> String {
+    markdown
+        .to_nodes(text)
+        .iter()
+        .map(|node: &Node<Dry>| node.to_string())
+        .collect()
+}
+
+fn to_html(text: &str) -> String {
+    render(&Markdown::new(), text)
+}
+
+#[test]
+fn links_and_images() {
+    assert_eq!(
+        to_html(r#"[Home](/ "Go home")"#),
+        r#"<p><a href="/" title="Go home">Home</a></p>"#
+    );
+    assert_eq!(
+        to_html(r#"![An image](https://example.com/image.png "Title")"#),
+        r#"<p><img src="https://example.com/image.png" alt="An image" title="Title"></p>"#
+    );
+}
+
+#[test]
+fn rejected_urls() {
+    assert_eq!(
+        to_html("[Click *me*](javascript:steal())"),
+        "<p>Click <em>me</em></p>"
+    );
+    assert_eq!(to_html("[Click](JavaScript:steal())"), "<p>Click</p>");
+    assert_eq!(to_html("<javascript:steal()>"), "<p>javascript:steal()</p>");
+    assert_eq!(to_html("[Click](data:text/html,steal)"), "<p>Click</p>");
+    assert_eq!(
+        to_html("![An image](data:image/png;base64,AAAA)"),
+        "<p>An image</p>"
+    );
+    assert_eq!(
+        to_html("![An image](javascript:steal())"),
+        "<p>An image</p>"
+    );
+}
+
+#[test]
+fn url_policy() {
+    let markdown = Markdown::new().html_policy(Policy::default().allow_url_schemes(["data"]));
+
+    assert_eq!(
+        render(&markdown, "![An image](data:image/png;base64,AAAA)"),
+        r#"<p><img src="data:image/png;base64,AAAA" alt="An image"></p>"#
+    );
+
+    let markdown = Markdown::new().html_policy(Policy::default().allow_relative_urls(false));
+
+    assert_eq!(render(&markdown, "[Home](/)"), "<p>Home</p>");
+}
+
+#[test]
+fn inline_html() {
+    assert_eq!(
+        to_html("Press <kbd>Ctrl</kbd> + <kbd>`C`</kbd>"),
+        "<p>Press <kbd>Ctrl</kbd> + <kbd><code>C</code></kbd></p>"
+    );
+    assert_eq!(
+        to_html(r#"Some <span onclick="steal()">1 &lt; 2</span> text"#),
+        "<p>Some <span>1 &lt; 2</span> text</p>"
+    );
+    assert_eq!(
+        to_html("A <script>steal()</script> script"),
+        "<p>A  script</p>"
+    );
+}
+
+#[test]
+fn html_blocks() {
+    assert_eq!(
+        to_html("<div onclick=\"steal()\">\n<p>Block</p>\n</div>\n\nText"),
+        "<div><p>Block</p></div><p>Text</p>"
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        to_html(
+            "| Left | Center | Right | None |\n\
+             |:-----|:------:|------:|------|\n\
+             | 1    | 2      | 3     | 4    |"
+        ),
+        "<table>\
+            <thead><tr>\
+                <th style=\"text-align: left;\">Left</th>\
+                <th style=\"text-align: center;\">Center</th>\
+                <th style=\"text-align: right;\">Right</th>\
+                <th>None</th>\
+            </tr></thead>\
+            <tbody><tr>\
+                <td style=\"text-align: left;\">1</td>\
+                <td style=\"text-align: center;\">2</td>\
+                <td style=\"text-align: right;\">3</td>\
+                <td>4</td>\
+            </tr></tbody>\
+        </table>"
+    );
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        to_html("Text[^note]\n\n[^note]: The note."),
+        "<p>Text<sup class=\"footnote-reference\"><a href=\"#note\">note</a></sup></p>\
+         <div class=\"footnote-definition\" id=\"note\">\
+            <sup class=\"footnote-definition-label\">note</sup><p>The note.</p>\
+         </div>"
+    );
+}
+
+#[test]
+fn task_lists() {
+    assert_eq!(
+        to_html("- [x] Done\n- [ ] Todo"),
+        "<ul>\
+            <li><input type=\"checkbox\" disabled=\"\" checked=\"\">Done</li>\
+            <li><input type=\"checkbox\" disabled=\"\">Todo</li>\
+        </ul>"
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        to_html("3. Three\n4. Four"),
+        r#"<ol start="3"><li>Three</li><li>Four</li></ol>"#
+    );
+    assert_eq!(to_html("1. One"), "<ol><li>One</li></ol>");
+}
+
+#[test]
+fn code_blocks() {
+    assert_eq!(
+        to_html("```rust\nfn main() {}\n```"),
+        "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>"
+    );
+    assert_eq!(
+        to_html("    <indented>\n"),
+        "<pre><code>&lt;indented&gt;\n</code></pre>"
+    );
+}
+
+#[test]
+fn options() {
+    let markdown = Markdown::new().options(Options::empty());
+
+    assert_eq!(render(&markdown, "~~Struck~~"), "<p>~~Struck~~</p>");
+    assert_eq!(to_html("~~Struck~~"), "<p><del>Struck</del></p>");
+}
+
+struct Custom;
+
+impl<D: Dom> Render<D> for Custom {
+    fn code_block(&self, language: Option<&str>, code_text: &str) -> Node<D> {
+        div()
+            .class("highlight")
+            .attribute("data-language", language.unwrap_or("none"))
+            .text(code_text)
+            .into()
+    }
+
+    fn link(&self, link: Link, children: Vec<Node<D>>) -> Node<D> {
+        span()
+            .class("link")
+            .attribute("data-dest", link.dest)
+            .children(children)
+            .into()
+    }
+
+    fn image(&self, image: Image) -> Node<D> {
+        span()
+            .class("image")
+            .attribute("data-dest", image.dest)
+            .text(image.alt)
+            .into()
+    }
+
+    fn heading(
+        &self,
+        level: u8,
+        _id: Option<&str>,
+        _classes: &[&str],
+        children: Vec<Node<D>>,
+    ) -> Node<D> {
+        div()
+            .class(format!("heading-{level}"))
+            .children(children)
+            .into()
+    }
+}
+
+#[test]
+fn overrides() {
+    let markdown = Markdown::new().renderer(Custom);
+
+    assert_eq!(
+        render(&markdown, "```rust ignore\ncode\n```"),
+        r#"<div class="highlight" data-language="rust">code
+</div>"#
+    );
+    assert_eq!(
+        render(&markdown, "[Home](/) ![*An* image](/image.png)"),
+        r#"<p><span class="link" data-dest="/">Home</span> <span class="image" data-dest="/image.png">An image</span></p>"#
+    );
+    assert_eq!(
+        render(&markdown, "### Heading"),
+        r#"<div class="heading-3">Heading</div>"#
+    );
+}
+
+#[test]
+fn overrides_dont_see_rejected_urls() {
+    let markdown = Markdown::new().renderer(Custom);
+
+    assert_eq!(
+        render(&markdown, "[Click](javascript:steal())"),
+        "<p>Click</p>"
+    );
+}
@@ -0,0 +1,112 @@
+//! `html_to_rust` uses the server side parser, so these only run natively.
+#![cfg(not(target_arch = "wasm32"))]
+use std::{fs, path::Path};
+
+use silkenweb_parse::html_to_rust;
+
+const USE_HTML: &str = "use silkenweb::{elements::html::*, prelude::*};\n";
+
+#[test]
+fn svg_children() {
+    assert_eq!(
+        html_to_rust("<svg><rect></rect></svg>"),
+        format!("{USE_HTML}\nsvg::svg().child(svg::rect())\n")
+    );
+}
+
+#[test]
+fn generic_element_fallback() {
+    assert_eq!(
+        html_to_rust("<my-widget>Text</my-widget>"),
+        r#"use silkenweb::{
+    elements::html::*,
+    node::element::{GenericElement, Namespace},
+    prelude::*,
+};
+
+GenericElement::new(&Namespace::Html, "my-widget").text("Text")
+"#
+    );
+}
+
+#[test]
+fn whitespace() {
+    assert_eq!(
+        html_to_rust("<div>  Some\n  text </div>"),
+        format!("{USE_HTML}\ndiv().text(\" Some text \")\n")
+    );
+    assert_eq!(
+        html_to_rust("<pre>  Some\n  text </pre>"),
+        format!("{USE_HTML}\npre().text(\"  Some\\n  text \")\n")
+    );
+}
+
+#[test]
+fn classes() {
+    assert_eq!(
+        html_to_rust(r#"<div class="card"></div>"#),
+        format!("{USE_HTML}\ndiv().class(\"card\")\n")
+    );
+    assert_eq!(
+        html_to_rust(r#"<div class=" card  large "></div>"#),
+        format!("{USE_HTML}\ndiv().classes([\"card\", \"large\"])\n")
+    );
+}
+
+#[test]
+fn wrapping() {
+    let items = "<li>First item in the list</li>\
+        <li>Second item in the list</li>\
+        <li>Third item in the list</li>";
+
+    assert_eq!(
+        html_to_rust(&format!("<ul>{items}</ul>")),
+        format!(
+            r#"{USE_HTML}
+ul()
+    .child(li().text("First item in the list"))
+    .child(li().text("Second item in the list"))
+    .child(li().text("Third item in the list"))
+"#
+        )
+    );
+
+    assert_eq!(
+        html_to_rust(&format!("<div><ul>{items}</ul></div>")),
+        format!(
+            r#"{USE_HTML}
+div()
+    .child(
+        ul()
+            .child(li().text("First item in the list"))
+            .child(li().text("Second item in the list"))
+            .child(li().text("Third item in the list"))
+    )
+"#
+        )
+    );
+}
+
+#[test]
+fn generated_code_compiles() {
+    let code = html_to_rust(
+        r#"<div class="card large" id="card">
+            <h1>Title</h1>
+            <svg><rect></rect></svg>
+            <my-widget>Custom</my-widget>
+            <pre> Preformatted </pre>
+            <ol start="3"><li>Item</li></ol>
+        </div>"#,
+    );
+    let (uses, expr) = code.split_once("\n\n").unwrap();
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("rust_code");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("generated_code_compiles.rs");
+    fs::write(
+        &path,
+        format!("{uses}\n\nfn main() {{\n    let _: Div = {expr};\n}}\n"),
+    )
+    .unwrap();
+
+    trybuild::TestCases::new().pass(path);
+}
@@ -0,0 +1,157 @@
+//! These run on the server and in the browser, with the same expected output,
+//! to check that sanitization doesn't depend on which parser is used.
+use silkenweb::{dom::Dry, node::Node};
+use silkenweb_parse::{html_to_sanitized_nodes, sanitize::Policy};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn sanitize(html: &str, policy: &Policy) -> String {
+    let nodes: Vec<Node<Dry>> = html_to_sanitized_nodes(html, policy);
+    nodes.iter().map(|node| node.to_string()).collect()
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn event_handlers() {
+    let policy = Policy::default().allow_attributes(["onclick", "onmouseover"]);
+
+    assert_eq!(
+        sanitize(
+            r#"<p onclick="steal()" ONMOUSEOVER="steal()" title="Title">Text</p>"#,
+            &policy
+        ),
+        r#"<p title="Title">Text</p>"#
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn javascript_urls() {
+    for href in [
+        "javascript:steal()",
+        "JavaScript:steal()",
+        " javascript:steal()",
+        "&#1;&#31;javascript:steal()",
+        "java&#9;script:steal()",
+        "java&#10;script:steal()",
+        "java&#13;script:steal()",
+    ] {
+        assert_eq!(
+            sanitize(&format!(r#"<a href="{href}">Link</a>"#), &Policy::default()),
+            "<a>Link</a>",
+            "{href}"
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn allowed_urls() {
+    for href in ["/page", "page?a=b:c", "#section", "https://example.com/"] {
+        assert_eq!(
+            sanitize(&format!(r#"<a href="{href}">Link</a>"#), &Policy::default()),
+            format!(r#"<a href="{href}">Link</a>"#)
+        );
+    }
+
+    assert_eq!(
+        sanitize(
+            r#"<a href="/page">Link</a>"#,
+            &Policy::default().allow_relative_urls(false)
+        ),
+        "<a>Link</a>"
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn url_lists() {
+    let policy = Policy::default()
+        .allow_element_attributes("img", ["srcset"])
+        .allow_element_attributes("a", ["ping"]);
+
+    assert_eq!(
+        sanitize(
+            r#"<img srcset="/small.png 1x, /large.png 2x"><img srcset="/small.png 1x, javascript:steal() 2x">"#,
+            &policy
+        ),
+        r#"<img srcset="/small.png 1x, /large.png 2x"><img>"#
+    );
+    assert_eq!(
+        sanitize(
+            r#"<a ping="/track https://example.com/track">1</a><a ping="/track javascript:steal()">2</a>"#,
+            &policy
+        ),
+        r#"<a ping="/track https://example.com/track">1</a><a>2</a>"#
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn removed_content() {
+    assert_eq!(
+        sanitize(
+            "<p>Before<script>steal()</script><style>p { color: red }</style><textarea>Text</textarea>After</p>",
+            &Policy::default()
+        ),
+        "<p>BeforeAfter</p>"
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn allowing_removed_content() {
+    let html = "<p>Before<script>steal()</script>After</p>";
+
+    assert_eq!(
+        sanitize(html, &Policy::default().allow_elements(["script"])),
+        "<p>BeforeAfter</p>"
+    );
+    assert_eq!(
+        sanitize(html, &Policy::default().allow_removed_content(["script"])),
+        "<p>Beforesteal()After</p>"
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn svg_and_mathml() {
+    assert_eq!(
+        sanitize(
+            r#"<p>Text<svg><a href="/page"><text>SVG</text></a></svg><math><mi>x</mi></math></p>"#,
+            &Policy::default().allow_elements(["svg", "text", "math", "mi"])
+        ),
+        "<p>Text</p>"
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn disallowed_elements_keep_children() {
+    assert_eq!(
+        sanitize(
+            r#"<form action="/steal"><p>Text <button>Button</button></p></form>"#,
+            &Policy::default()
+        ),
+        "<p>Text Button</p>"
+    );
+}
+
+#[wasm_bindgen_test]
+#[test]
+fn case_insensitive_policy() {
+    let policy = Policy::empty()
+        .allow_elements(["P", "A"])
+        .allow_attributes(["TITLE"])
+        .allow_element_attributes("A", ["HREF"])
+        .allow_url_schemes(["HTTPS"]);
+
+    assert_eq!(
+        sanitize(
+            r#"<p title="Title"><a href="https://example.com/">Link</a></p>"#,
+            &policy
+        ),
+        r#"<p title="Title"><a href="https://example.com/">Link</a></p>"#
+    );
+}
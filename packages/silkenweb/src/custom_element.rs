@@ -0,0 +1,490 @@
+//! Define custom elements (Web Components) from Silkenweb components.
+//!
+//! Implement [`CustomElement`] for a type, and register it with [`define`].
+//! The element can then be used from plain HTML or JavaScript, like any other
+//! custom element.
+//!
+//! The element's content is rendered into an open shadow root. Observed
+//! attributes are available as signals with [`Host::attribute`], JavaScript
+//! properties as [`Mutable`]s with [`Host::property`], and events can be sent
+//! to the outside world with [`Host::dispatch_event`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use html::{button, span};
+//! # use silkenweb::{
+//! #     custom_element::{define, CustomElement, Host},
+//! #     dom::Wet,
+//! #     node::element::{Const, GenericElement},
+//! #     prelude::*,
+//! # };
+//! # use wasm_bindgen::JsValue;
+//! struct Greeting {
+//!     host: Host,
+//! }
+//!
+//! impl CustomElement for Greeting {
+//!     const OBSERVED_ATTRIBUTES: &'static [&'static str] = &["name"];
+//!
+//!     fn new(host: &Host) -> Self {
+//!         Self { host: host.clone() }
+//!     }
+//!
+//!     fn render(&self) -> GenericElement<Wet, Const> {
+//!         let name = self
+//!             .host
+//!             .attribute("name")
+//!             .map(|name| format!("Hello, {}!", name.unwrap_or_default()));
+//!         let host = self.host.clone();
+//!
+//!         span()
+//!             .text(Sig(name))
+//!             .child(button().text("Wave").on_click(move |_, _| {
+//!                 host.dispatch_event("wave", &JsValue::UNDEFINED);
+//!             }))
+//!             .into()
+//!     }
+//! }
+//!
+//! define::<Greeting>("my-greeting").unwrap();
+//! ```
+//!
+//! This can be used from HTML with `<my-greeting name="World"></my-greeting>`.
+use std::{collections::HashMap, rc::Rc};
+
+use futures_signals::signal::{Mutable, Signal};
+use silkenweb_macros::cfg_browser;
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
+
+use crate::{
+    dom::Wet,
+    node::element::{Const, GenericElement},
+};
+
+/// A Silkenweb component that can be registered as a custom element.
+///
+/// A new value is created when an element is connected to the document, and
+/// dropped when it's disconnected. Moving an element within the document keeps
+/// the same value, but if an element is removed and added again later, it gets
+/// a new value and its shadow root is rendered again. JavaScript property
+/// values are kept while the element is disconnected.
+///
+/// The value can't live as long as the element itself. It holds the host
+/// element and the shadow root content, which would keep the element alive
+/// forever.
+pub trait CustomElement: Sized + 'static {
+    /// The attributes to observe.
+    ///
+    /// These are available as signals with [`Host::attribute`].
+    const OBSERVED_ATTRIBUTES: &'static [&'static str] = &[];
+
+    /// The JavaScript properties to define on the element.
+    ///
+    /// These are available as [`Mutable`]s with [`Host::property`].
+    const PROPERTIES: &'static [&'static str] = &[];
+
+    /// Construct the element state.
+    fn new(host: &Host) -> Self;
+
+    /// Render the contents of the element's shadow root.
+    ///
+    /// This is called once, just after [`CustomElement::new`].
+    fn render(&self) -> GenericElement<Wet, Const>;
+
+    /// Called when the element is added to the document.
+    ///
+    /// This is called just after [`CustomElement::render`] for a new value.
+    fn connected(&self) {}
+
+    /// Called when the element is removed from the document.
+    fn disconnected(&self) {}
+
+    /// Called when the element is moved to a new document.
+    fn adopted(&self) {}
+}
+
+/// Register `T` as a custom element called `name`.
+///
+/// `name` must contain a hyphen, and can only be defined once. See
+/// [`CustomElementRegistry.define()`] for details.
+///
+/// On the server, this does nothing.
+///
+/// [`CustomElementRegistry.define()`]: https://developer.mozilla.org/en-US/docs/Web/API/CustomElementRegistry/define
+pub fn define<T: CustomElement>(name: &str) -> Result<(), JsValue> {
+    arch::define::<T>(name)
+}
+
+/// The host element of a [`CustomElement`].
+///
+/// Cloning a `Host` gives another handle to the same element.
+#[derive(Clone)]
+pub struct Host(Rc<HostState>);
+
+struct HostState {
+    element: web_sys::HtmlElement,
+    attributes: HashMap<&'static str, Mutable<Option<String>>>,
+    properties: HashMap<&'static str, Mutable<JsValue>>,
+}
+
+impl Host {
+    #[cfg_browser(true)]
+    fn new(
+        element: web_sys::HtmlElement,
+        observed_attributes: &[&'static str],
+        properties: &[&'static str],
+    ) -> Self {
+        let attributes = observed_attributes
+            .iter()
+            .map(|&name| (name, Mutable::new(element.get_attribute(name))))
+            .collect();
+        let properties = properties
+            .iter()
+            .map(|&name| (name, Mutable::new(JsValue::UNDEFINED)))
+            .collect();
+
+        Self(Rc::new(HostState {
+            element,
+            attributes,
+            properties,
+        }))
+    }
+
+    /// The host DOM element.
+    pub fn element(&self) -> &web_sys::HtmlElement {
+        &self.0.element
+    }
+
+    /// A signal of the value of an observed attribute.
+    ///
+    /// # Panics
+    ///
+    /// If `name` isn't in [`CustomElement::OBSERVED_ATTRIBUTES`].
+    pub fn attribute(&self, name: &str) -> impl Signal<Item = Option<String>> + 'static {
+        self.attribute_mutable(name).signal_cloned()
+    }
+
+    /// The value of a JavaScript property.
+    ///
+    /// Setting the property from JavaScript will update the [`Mutable`], and
+    /// setting the [`Mutable`] will update what JavaScript sees.
+    ///
+    /// # Panics
+    ///
+    /// If `name` isn't in [`CustomElement::PROPERTIES`].
+    pub fn property(&self, name: &str) -> Mutable<JsValue> {
+        self.0
+            .properties
+            .get(name)
+            .unwrap_or_else(|| panic!("`{name}` isn't a property of this custom element"))
+            .clone()
+    }
+
+    /// Dispatch a [`CustomEvent`] from the host element.
+    ///
+    /// The event bubbles and crosses shadow DOM boundaries. `detail` is
+    /// available to listeners as `event.detail`. The return value is `false`
+    /// if a listener cancelled the event.
+    ///
+    /// [`CustomEvent`]: https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent
+    pub fn dispatch_event(&self, name: &str, detail: &JsValue) -> bool {
+        let mut init = web_sys::CustomEventInit::new();
+        init.bubbles(true)
+            .composed(true)
+            .cancelable(true)
+            .detail(detail);
+        let event = web_sys::CustomEvent::new_with_event_init_dict(name, &init).unwrap_throw();
+
+        self.0.element.dispatch_event(&event).unwrap_throw()
+    }
+
+    fn attribute_mutable(&self, name: &str) -> &Mutable<Option<String>> {
+        self.0
+            .attributes
+            .get(name)
+            .unwrap_or_else(|| panic!("`{name}` isn't observed by this custom element"))
+    }
+}
+
+#[cfg_browser(false)]
+mod arch {
+    use wasm_bindgen::JsValue;
+
+    use super::CustomElement;
+
+    pub fn define<T: CustomElement>(_name: &str) -> Result<(), JsValue> {
+        Ok(())
+    }
+}
+
+#[cfg_browser(true)]
+mod arch {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        rc::Rc,
+    };
+
+    use wasm_bindgen::{
+        prelude::{wasm_bindgen, Closure},
+        JsValue, UnwrapThrowExt,
+    };
+    use web_sys::{ShadowRootInit, ShadowRootMode};
+
+    use super::{CustomElement, Host};
+    use crate::{
+        dom::Wet,
+        node::element::{Const, GenericElement},
+    };
+
+    pub fn define<T: CustomElement>(name: &str) -> Result<(), JsValue> {
+        let construct =
+            Closure::wrap(Box::new(construct::<T>) as Box<dyn FnMut(web_sys::HtmlElement) -> u32>);
+        let lifecycle = Closure::wrap(Box::new(|id: u32, callback: String| {
+            if let Some(instance) = instance(id) {
+                match callback.as_str() {
+                    "connected" => instance.element.connected(),
+                    "disconnected" => instance.element.disconnected(),
+                    "adopted" => instance.element.adopted(),
+                    _ => (),
+                }
+            }
+        }) as Box<dyn FnMut(u32, String)>);
+        let attribute_changed =
+            Closure::wrap(Box::new(|id: u32, name: String, value: Option<String>| {
+                if let Some(instance) = instance(id) {
+                    instance.host.attribute_mutable(&name).set_neq(value);
+                }
+            })
+                as Box<dyn FnMut(u32, String, Option<String>)>);
+        let get_property = Closure::wrap(Box::new(|id: u32, name: String| {
+            instance(id)
+                .map(|instance| instance.host.property(&name).get_cloned())
+                .unwrap_or(JsValue::UNDEFINED)
+        }) as Box<dyn FnMut(u32, String) -> JsValue>);
+        let set_property = Closure::wrap(Box::new(|id: u32, name: String, value: JsValue| {
+            if let Some(instance) = instance(id) {
+                instance.host.property(&name).set(value);
+            }
+        }) as Box<dyn FnMut(u32, String, JsValue)>);
+        let release = Closure::wrap(Box::new(|id: u32| {
+            // Drop the instance after releasing the borrow, as dropping it may
+            // disconnect other custom elements.
+            let instance = INSTANCES.with(|instances| instances.borrow_mut().remove(&id));
+            drop(instance);
+        }) as Box<dyn FnMut(u32)>);
+
+        define_custom_element(
+            name,
+            js_strings(T::OBSERVED_ATTRIBUTES),
+            js_strings(T::PROPERTIES),
+            &construct,
+            &lifecycle,
+            &attribute_changed,
+            &get_property,
+            &set_property,
+            &release,
+        )?;
+
+        // Custom element definitions are permanent, so their callbacks must be too.
+        construct.forget();
+        lifecycle.forget();
+        attribute_changed.forget();
+        get_property.forget();
+        set_property.forget();
+        release.forget();
+
+        Ok(())
+    }
+
+    fn construct<T: CustomElement>(element: web_sys::HtmlElement) -> u32 {
+        let host = Host::new(element, T::OBSERVED_ATTRIBUTES, T::PROPERTIES);
+        let custom_element = T::new(&host);
+        let content = custom_element.render();
+        // The element may have been rendered before, if it was removed from the
+        // document and added again.
+        let shadow_root = match host.element().shadow_root() {
+            Some(shadow_root) => {
+                shadow_root.set_inner_html("");
+                shadow_root
+            }
+            None => host
+                .element()
+                .attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
+                .unwrap_throw(),
+        };
+        shadow_root
+            .append_child(&content.dom_element())
+            .unwrap_throw();
+
+        let id = NEXT_ID.with(|next_id| next_id.replace(next_id.get().wrapping_add(1)));
+        let instance = Rc::new(Instance {
+            host,
+            element: Box::new(custom_element),
+            _content: content,
+        });
+        INSTANCES.with(|instances| instances.borrow_mut().insert(id, instance));
+
+        id
+    }
+
+    /// Get an instance, without holding a borrow of `INSTANCES`, as callbacks
+    /// may construct other custom elements.
+    fn instance(id: u32) -> Option<Rc<Instance>> {
+        INSTANCES.with(|instances| instances.borrow().get(&id).cloned())
+    }
+
+    fn js_strings(strings: &[&str]) -> Box<[JsValue]> {
+        strings.iter().map(|s| JsValue::from_str(s)).collect()
+    }
+
+    struct Instance {
+        host: Host,
+        element: Box<dyn Lifecycle>,
+        _content: GenericElement<Wet, Const>,
+    }
+
+    trait Lifecycle {
+        fn connected(&self);
+
+        fn disconnected(&self);
+
+        fn adopted(&self);
+    }
+
+    impl<T: CustomElement> Lifecycle for T {
+        fn connected(&self) {
+            CustomElement::connected(self)
+        }
+
+        fn disconnected(&self) {
+            CustomElement::disconnected(self)
+        }
+
+        fn adopted(&self) {
+            CustomElement::adopted(self)
+        }
+    }
+
+    thread_local! {
+        static NEXT_ID: Cell<u32> = const { Cell::new(0) };
+        static INSTANCES: RefCell<HashMap<u32, Rc<Instance>>> = RefCell::new(HashMap::new());
+    }
+
+    #[wasm_bindgen(inline_js = r#"
+        export function defineCustomElement(
+            name,
+            observedAttributes,
+            properties,
+            construct,
+            lifecycle,
+            attributeChanged,
+            getProperty,
+            setProperty,
+            release,
+        ) {
+            // The Rust instance holds the element, so it can't be collected
+            // while the instance exists. Instances only exist while the element
+            // is connected, and property values are stored here otherwise.
+            class SilkenwebElement extends HTMLElement {
+                static get observedAttributes() {
+                    return observedAttributes;
+                }
+
+                constructor() {
+                    super();
+                    this.__silkenwebId = undefined;
+                    this.__silkenwebProperties = new Map();
+                }
+
+                connectedCallback() {
+                    if (this.__silkenwebId === undefined) {
+                        const id = construct(this);
+                        this.__silkenwebId = id;
+
+                        for (const [property, value] of this.__silkenwebProperties) {
+                            setProperty(id, property, value);
+                        }
+
+                        this.__silkenwebProperties.clear();
+                    }
+
+                    lifecycle(this.__silkenwebId, "connected");
+                }
+
+                disconnectedCallback() {
+                    const id = this.__silkenwebId;
+
+                    if (id === undefined) {
+                        return;
+                    }
+
+                    lifecycle(id, "disconnected");
+
+                    // Moving an element disconnects and then reconnects it, so
+                    // wait to see if it's still disconnected.
+                    queueMicrotask(() => {
+                        if (!this.isConnected && this.__silkenwebId === id) {
+                            for (const property of properties) {
+                                this.__silkenwebProperties.set(property, getProperty(id, property));
+                            }
+
+                            this.__silkenwebId = undefined;
+                            release(id);
+                        }
+                    });
+                }
+
+                adoptedCallback() {
+                    if (this.__silkenwebId !== undefined) {
+                        lifecycle(this.__silkenwebId, "adopted");
+                    }
+                }
+
+                attributeChangedCallback(name, _oldValue, newValue) {
+                    // New instances read the current attributes.
+                    if (this.__silkenwebId !== undefined) {
+                        attributeChanged(this.__silkenwebId, name, newValue);
+                    }
+                }
+            }
+
+            for (const property of properties) {
+                Object.defineProperty(SilkenwebElement.prototype, property, {
+                    get() {
+                        if (this.__silkenwebId === undefined) {
+                            return this.__silkenwebProperties.get(property);
+                        }
+
+                        return getProperty(this.__silkenwebId, property);
+                    },
+                    set(value) {
+                        if (this.__silkenwebId === undefined) {
+                            this.__silkenwebProperties.set(property, value);
+                        } else {
+                            setProperty(this.__silkenwebId, property, value);
+                        }
+                    },
+                });
+            }
+
+            customElements.define(name, SilkenwebElement);
+        }
+    "#)]
+    extern "C" {
+        #[allow(clippy::too_many_arguments)]
+        #[wasm_bindgen(js_name = defineCustomElement, catch)]
+        fn define_custom_element(
+            name: &str,
+            observed_attributes: Box<[JsValue]>,
+            properties: Box<[JsValue]>,
+            construct: &Closure<dyn FnMut(web_sys::HtmlElement) -> u32>,
+            lifecycle: &Closure<dyn FnMut(u32, String)>,
+            attribute_changed: &Closure<dyn FnMut(u32, String, Option<String>)>,
+            get_property: &Closure<dyn FnMut(u32, String) -> JsValue>,
+            set_property: &Closure<dyn FnMut(u32, String, JsValue)>,
+            release: &Closure<dyn FnMut(u32)>,
+        ) -> Result<(), JsValue>;
+    }
+}
@@ -0,0 +1,340 @@
+//! Typed CSS custom properties.
+//!
+//! [`css!`] generates a `property` module with a [`CustomProperty`] for each
+//! custom property registered with an [`@property`] rule. The type of the
+//! property is determined by its `syntax` descriptor:
+//!
+//! | `syntax`                | Type                  |
+//! |-------------------------|-----------------------|
+//! | `<color>`               | [`Color`]             |
+//! | `<length>`              | [`Length`]            |
+//! | `<percentage>`          | [`Percentage`]        |
+//! | `<length-percentage>`   | [`LengthPercentage`]  |
+//! | `<number>`              | [`f64`]               |
+//! | `<integer>`             | [`i64`]               |
+//! | `<angle>`               | [`Angle`]             |
+//! | `<time>`                | [`Time`]              |
+//! | `*`, or anything else   | [`String`]            |
+//!
+//! Numeric values are clamped to finite values, as CSS has no representation
+//! for `NaN` or infinity. `NaN` becomes `0`, and the infinities become
+//! [`f64::MAX`] and [`f64::MIN`].
+//!
+//! # Example
+//!
+//! ```
+//! # use html::{div, Div};
+//! # use silkenweb::{
+//! #     css,
+//! #     custom_property::{Color, Length, StyleCustomProperty},
+//! #     prelude::*,
+//! # };
+//! css!(
+//!     content = r#"
+//!         @property --accent {
+//!             syntax: '<color>';
+//!             inherits: true;
+//!             initial-value: rebeccapurple;
+//!         }
+//!
+//!         @property --gap {
+//!             syntax: '<length>';
+//!             inherits: false;
+//!             initial-value: 0px;
+//!         }
+//!     "#
+//! );
+//!
+//! assert_eq!(property::ACCENT.name(), "--accent");
+//! assert_eq!(property::ACCENT.initial_value(), Some("rebeccapurple"));
+//!
+//! let app: Div = div()
+//!     .custom_property(property::ACCENT, Color::rgb(255, 0, 0))
+//!     .custom_property(property::GAP, Length::px(4.0));
+//! ```
+//!
+//! [`css!`]: crate::css
+//! [`@property`]: https://developer.mozilla.org/en-US/docs/Web/CSS/@property
+use std::{fmt, marker::PhantomData};
+
+use crate::{
+    node::element::Element,
+    value::{RefSignalOrValue, Value},
+};
+
+/// A CSS custom property, with values of type `T`.
+pub struct CustomProperty<T> {
+    name: &'static str,
+    initial_value: Option<&'static str>,
+    phantom: PhantomData<fn(T)>,
+}
+
+impl<T> CustomProperty<T> {
+    /// `name` should include the `--` prefix.
+    pub const fn new(name: &'static str, initial_value: Option<&'static str>) -> Self {
+        Self {
+            name,
+            initial_value,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The property name, including the `--` prefix.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The `initial-value` from the `@property` rule.
+    pub const fn initial_value(&self) -> Option<&'static str> {
+        self.initial_value
+    }
+}
+
+impl<T> Clone for CustomProperty<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CustomProperty<T> {}
+
+/// Set typed custom properties on an element.
+pub trait StyleCustomProperty: Element {
+    /// Set a custom property in the element's style.
+    ///
+    /// The value can be a value or a signal. Signals should be wrapped in the
+    /// [`Sig`] newtype.
+    ///
+    /// [`Sig`]: crate::value::Sig
+    fn custom_property<'a, T>(
+        self,
+        property: CustomProperty<T>,
+        value: impl RefSignalOrValue<'a, Item = T>,
+    ) -> Self
+    where
+        T: PropertyValue + 'a,
+    {
+        self.style_property(property.name(), value.map(|value| value.to_css()))
+    }
+}
+
+impl<E: Element> StyleCustomProperty for E {}
+
+/// A value that can be assigned to a [`CustomProperty`].
+pub trait PropertyValue {
+    /// The CSS text for the value.
+    fn to_css(&self) -> String;
+}
+
+impl PropertyValue for f64 {
+    fn to_css(&self) -> String {
+        finite(*self).to_string()
+    }
+}
+
+impl PropertyValue for i64 {
+    fn to_css(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl PropertyValue for String {
+    fn to_css(&self) -> String {
+        self.clone()
+    }
+}
+
+macro_rules! property_values {
+    ($($typ:ident),*) => {$(
+        impl PropertyValue for $typ {
+            fn to_css(&self) -> String {
+                self.to_string()
+            }
+        }
+
+        impl Value for $typ {}
+    )*};
+}
+
+property_values!(Color, Length, Percentage, LengthPercentage, Angle, Time);
+
+/// A CSS `<color>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Color(String);
+
+impl Color {
+    /// An opaque color from red, green and blue components.
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self(format!("rgb({red} {green} {blue})"))
+    }
+
+    /// `alpha` is between 0 and 1.
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: f64) -> Self {
+        let alpha = finite(alpha);
+        Self(format!("rgb({red} {green} {blue} / {alpha})"))
+    }
+
+    /// `hue` is in degrees. `saturation` and `lightness` are percentages.
+    pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let (hue, saturation, lightness) = (finite(hue), finite(saturation), finite(lightness));
+        Self(format!("hsl({hue}deg {saturation}% {lightness}%)"))
+    }
+
+    /// A color from a `0xRRGGBB` value.
+    pub fn hex(rgb: u32) -> Self {
+        Self(format!("#{:06x}", rgb & 0xff_ffff))
+    }
+
+    /// A named color, like `"rebeccapurple"`.
+    ///
+    /// The name isn't checked.
+    pub fn named(name: &str) -> Self {
+        Self(name.to_string())
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A CSS `<length>`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Length(f64, &'static str);
+
+impl Length {
+    /// A zero length.
+    pub const ZERO: Self = Self(0.0, "px");
+
+    /// A length in pixels.
+    pub fn px(value: f64) -> Self {
+        Self(value, "px")
+    }
+
+    /// A length relative to the element's font size.
+    pub fn em(value: f64) -> Self {
+        Self(value, "em")
+    }
+
+    /// A length relative to the root element's font size.
+    pub fn rem(value: f64) -> Self {
+        Self(value, "rem")
+    }
+
+    /// A length relative to the width of the `"0"` glyph.
+    pub fn ch(value: f64) -> Self {
+        Self(value, "ch")
+    }
+
+    /// A length as a percentage of the viewport width.
+    pub fn vw(value: f64) -> Self {
+        Self(value, "vw")
+    }
+
+    /// A length as a percentage of the viewport height.
+    pub fn vh(value: f64) -> Self {
+        Self(value, "vh")
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", finite(self.0), self.1)
+    }
+}
+
+/// A CSS `<percentage>`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Percentage(pub f64);
+
+impl fmt::Display for Percentage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", finite(self.0))
+    }
+}
+
+/// A CSS `<length-percentage>`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LengthPercentage {
+    Length(Length),
+    Percentage(Percentage),
+}
+
+impl From<Length> for LengthPercentage {
+    fn from(value: Length) -> Self {
+        Self::Length(value)
+    }
+}
+
+impl From<Percentage> for LengthPercentage {
+    fn from(value: Percentage) -> Self {
+        Self::Percentage(value)
+    }
+}
+
+impl fmt::Display for LengthPercentage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(length) => length.fmt(f),
+            Self::Percentage(percentage) => percentage.fmt(f),
+        }
+    }
+}
+
+/// A CSS `<angle>`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Angle(f64, &'static str);
+
+impl Angle {
+    /// An angle in degrees.
+    pub fn deg(value: f64) -> Self {
+        Self(value, "deg")
+    }
+
+    /// An angle in radians.
+    pub fn rad(value: f64) -> Self {
+        Self(value, "rad")
+    }
+
+    /// An angle in turns, where one turn is a full circle.
+    pub fn turn(value: f64) -> Self {
+        Self(value, "turn")
+    }
+}
+
+impl fmt::Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", finite(self.0), self.1)
+    }
+}
+
+/// A CSS `<time>`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Time(f64, &'static str);
+
+impl Time {
+    /// A time in seconds.
+    pub fn s(value: f64) -> Self {
+        Self(value, "s")
+    }
+
+    /// A time in milliseconds.
+    pub fn ms(value: f64) -> Self {
+        Self(value, "ms")
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", finite(self.0), self.1)
+    }
+}
+
+fn finite(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(f64::MIN, f64::MAX)
+    }
+}
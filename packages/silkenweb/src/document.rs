@@ -1,8 +1,9 @@
 //! Document utilities.
 use std::{
     cell::RefCell,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     pin::{pin, Pin},
+    rc::Rc,
     task,
 };
 
@@ -19,12 +20,13 @@ use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 use crate::{
     dom::{self, Dom, Dry, Wet},
+    elements::html::style,
     event::{bubbling_events, GlobalEventCallback},
     hydration::HydrationStats,
     node::{
         element::{
             child_vec::{ChildVecHandle, ParentShared},
-            Const, Element, GenericElement,
+            Const, Element, GenericElement, ParentElement,
         },
         Node,
     },
@@ -111,9 +113,29 @@ pub trait Document: Dom + Sized {
     /// Mounting something with the same `id` will cause a panic.
     fn mount_in_head(id: &str, head: DocumentHead<Self>) -> Self::MountInHeadOutput;
 
+    /// Mount a stylesheet in the document `<head>`, unless a stylesheet with
+    /// the same `id` is already mounted.
+    ///
+    /// This is used by the `stylesheet` module generated by [`css!`]. On
+    /// [`Dry`] DOMs, stylesheets are tracked per [`task::scope`], so
+    /// [`head_inner_html`] only includes the stylesheets used by the current
+    /// render, in the order they were first mounted. See
+    /// [`link_stylesheets`] to render them as links instead of inline.
+    ///
+    /// On browser DOMs, a stylesheet that's already in the `<head>`, for
+    /// example from a server side render, is left as it is. This is the case
+    /// whether it was rendered inline or as a link.
+    ///
+    /// [`css!`]: crate::css
+    /// [`task::scope`]: crate::task::scope
+    /// [`head_inner_html`]: Document::head_inner_html
+    fn mount_stylesheet(id: &'static str, text: &'static str);
+
     /// Remove all mounted elements.
     ///
-    /// All elements mounted with `mount` or `mount_in_head` will be removed.
+    /// All elements mounted with `mount`, `mount_in_head` or
+    /// `mount_stylesheet` will be removed, and on [`Dry`] DOMs,
+    /// [`link_stylesheets`] is reset.
     /// Mount points will not be restored. This is useful to ensure a clean
     /// environment for testing.
     fn unmount_all();
@@ -126,6 +148,75 @@ pub trait Document: Dom + Sized {
     fn head_inner_html() -> String;
 }
 
+/// A stylesheet mounted with [`Document::mount_stylesheet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stylesheet {
+    id: &'static str,
+    text: &'static str,
+}
+
+impl Stylesheet {
+    /// The stylesheet's `id`, which is unique for each [`css!`] invocation.
+    ///
+    /// [`css!`]: crate::css
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    /// The content of the stylesheet.
+    pub fn text(&self) -> &'static str {
+        self.text
+    }
+}
+
+/// Get the stylesheets used by the current server side render.
+///
+/// These are the stylesheets that have been mounted with
+/// [`Document::mount_stylesheet`] on the [`Dry`] DOM in the current
+/// [`task::scope`][crate::task::scope], in the order they were first mounted.
+/// Stylesheets from [`css!`] are mounted when `stylesheet::mount` is called,
+/// or, with `auto_mount`, when any of their classes or variables are used.
+///
+/// [`css!`]: crate::css
+pub fn used_stylesheets() -> Vec<Stylesheet> {
+    crate::task::local::with(|local| local.document.dry_stylesheets.borrow().clone())
+}
+
+/// Get the ids of the `<head>` entries used by the current server side render.
+///
+/// Each top level element in [`Dry::head_inner_html`] has a
+/// `data-silkenweb-head-id` attribute with one of these ids. Stylesheets use
+/// their [`Stylesheet::id`], and elements mounted with
+/// [`Document::mount_in_head`] use the `id` they were mounted with.
+///
+/// [`Dry::head_inner_html`]: Document::head_inner_html
+pub fn used_head_ids() -> Vec<String> {
+    crate::task::local::with(|local| {
+        let stylesheets = local.document.dry_stylesheets.borrow();
+        let mounted = local.document.mounted_in_dry_head.borrow();
+
+        stylesheets
+            .iter()
+            .map(|stylesheet| stylesheet.id.to_string())
+            .chain(mounted.keys().cloned())
+            .collect()
+    })
+}
+
+/// Render stylesheets as links in [`Dry::head_inner_html`].
+///
+/// By default, stylesheets are inlined in `<style>` elements. After calling
+/// this, [`Dry::head_inner_html`] will render each stylesheet as a
+/// `<link rel="stylesheet">`, with the `href` given by `href`. The server is
+/// responsible for serving the [`Stylesheet::text`] at that location.
+///
+/// This only applies to the current [`task::scope`][crate::task::scope].
+///
+/// [`Dry::head_inner_html`]: Document::head_inner_html
+pub fn link_stylesheets(href: impl Fn(&Stylesheet) -> String + 'static) {
+    crate::task::local::with(|local| local.document.stylesheet_href.replace(Some(Rc::new(href))));
+}
+
 /// The document's `<head>` element.
 ///
 /// This allows you to create a set of (possibly reactive) children that can be
@@ -268,8 +359,12 @@ fn children_with_id<D: Dom>(head: DocumentHead<D>, id: &str) -> impl SignalVec<I
 #[derive(Default)]
 pub(crate) struct TaskLocal {
     mounted_in_dry_head: RefCell<HashMap<String, ChildVecHandle<Dry, ParentShared>>>,
+    dry_stylesheets: RefCell<Vec<Stylesheet>>,
+    stylesheet_href: RefCell<Option<StylesheetHref>>,
 }
 
+type StylesheetHref = Rc<dyn Fn(&Stylesheet) -> String>;
+
 fn wet_insert_mounted(id: &str, element: GenericElement<Wet, Const>) {
     let existing = WET_MOUNTED.with(|mounted| mounted.borrow_mut().insert(id.to_string(), element));
 
@@ -283,6 +378,34 @@ fn wet_unmount() {
     for element in WET_MOUNTED.take().into_values() {
         element.dom_element().remove()
     }
+
+    WET_STYLESHEETS.with(|stylesheets| stylesheets.borrow_mut().clear());
+}
+
+fn wet_mount_stylesheet<D: Document>(id: &'static str, text: &'static str) {
+    // The server may have rendered the stylesheet inline or as a link (see
+    // `link_stylesheets`). Either way, we leave it as it is, rather than
+    // hydrating a `<style>` against it.
+    if WET_STYLESHEETS.with(|stylesheets| stylesheets.borrow_mut().insert(id))
+        && !in_document_head(id)
+    {
+        D::mount_in_head(id, DocumentHead::new().child(style().text(text)));
+    }
+}
+
+/// Is there an element with head id `id` in the document `<head>`?
+fn in_document_head(id: &str) -> bool {
+    let mut child = document::head().unwrap_throw().first_element_child();
+
+    while let Some(elem) = child {
+        if elem.get_attribute(HEAD_ID_ATTRIBUTE).as_deref() == Some(id) {
+            return true;
+        }
+
+        child = elem.next_element_sibling();
+    }
+
+    false
 }
 
 struct MountedInHead<D: Dom>(RefCell<HashMap<String, ChildVecHandle<D, ParentShared>>>);
@@ -320,4 +443,5 @@ impl<D: Dom> MountedInHead<D> {
 
 thread_local! {
     static WET_MOUNTED: RefCell<HashMap<String, GenericElement<Wet, Const>>> = RefCell::new(HashMap::new());
+    static WET_STYLESHEETS: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
 }
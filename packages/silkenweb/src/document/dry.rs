@@ -1,12 +1,13 @@
-use super::{Document, DocumentHead};
+use super::{Document, DocumentHead, Stylesheet};
 use crate::{
     document::children_with_id,
     dom::{self, private::DomElement, Dry},
+    elements::html::{link, style},
     node::element::{
         child_vec::{ChildVec, ParentShared},
-        Const, GenericElement, Namespace,
+        Const, Element, GenericElement, Namespace, ParentElement,
     },
-    task,
+    task, HEAD_ID_ATTRIBUTE,
 };
 
 impl Document for Dry {
@@ -36,14 +37,47 @@ impl Document for Dry {
         );
     }
 
+    fn mount_stylesheet(id: &'static str, text: &'static str) {
+        task::local::with(|local| {
+            let mut stylesheets = local.document.dry_stylesheets.borrow_mut();
+
+            if stylesheets.iter().all(|stylesheet| stylesheet.id != id) {
+                stylesheets.push(Stylesheet { id, text });
+            }
+        });
+    }
+
     fn unmount_all() {
-        task::local::with(|local| local.document.mounted_in_dry_head.take());
+        task::local::with(|local| {
+            local.document.mounted_in_dry_head.take();
+            local.document.dry_stylesheets.take();
+            local.document.stylesheet_href.take();
+        });
     }
 
     fn head_inner_html() -> String {
         let mut html = String::new();
 
         task::local::with(|local| {
+            let href = local.document.stylesheet_href.borrow();
+
+            for stylesheet in local.document.dry_stylesheets.borrow().iter() {
+                let elem: GenericElement<Dry, Const> = if let Some(href) = href.as_ref() {
+                    link::<Dry>()
+                        .rel("stylesheet")
+                        .href(href(stylesheet))
+                        .attribute(HEAD_ID_ATTRIBUTE, stylesheet.id)
+                        .into()
+                } else {
+                    style::<Dry>()
+                        .attribute(HEAD_ID_ATTRIBUTE, stylesheet.id)
+                        .text(stylesheet.text)
+                        .into()
+                };
+
+                html.push_str(&elem.to_string());
+            }
+
             for elem in local.document.mounted_in_dry_head.borrow().values() {
                 html.push_str(&elem.inner_html());
             }
@@ -2,8 +2,8 @@ use futures::channel::oneshot;
 use silkenweb_task::spawn_local;
 
 use super::{
-    children_with_id, document_head, wet_insert_mounted, wet_unmount, Document, MountHydro,
-    MountHydroHead,
+    children_with_id, document_head, wet_insert_mounted, wet_mount_stylesheet, wet_unmount,
+    Document, MountHydro, MountHydroHead,
 };
 use crate::{
     document::MountedInHead,
@@ -62,6 +62,10 @@ impl Document for Hydro {
         MountHydroHead(receive)
     }
 
+    fn mount_stylesheet(id: &'static str, text: &'static str) {
+        wet_mount_stylesheet::<Self>(id, text);
+    }
+
     fn unmount_all() {
         wet_unmount();
         MOUNTED_IN_HEAD.with(|m| m.unmount_all());
@@ -1,7 +1,8 @@
 use wasm_bindgen::UnwrapThrowExt;
 
 use super::{
-    children_with_id, document_head, wet_insert_mounted, wet_unmount, Document, DocumentHead,
+    children_with_id, document_head, wet_insert_mounted, wet_mount_stylesheet, wet_unmount,
+    Document, DocumentHead,
 };
 use crate::{
     document::MountedInHead,
@@ -34,6 +35,10 @@ impl Document for Wet {
         MOUNTED_IN_HEAD.with(|m| m.mount(id, child_vec_handle));
     }
 
+    fn mount_stylesheet(id: &'static str, text: &'static str) {
+        wet_mount_stylesheet::<Self>(id, text);
+    }
+
     fn unmount_all() {
         wet_unmount();
         MOUNTED_IN_HEAD.with(|m| m.unmount_all());
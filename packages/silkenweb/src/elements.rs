@@ -35,6 +35,8 @@ use wasm_bindgen::JsCast;
 use crate::node::element::Element;
 
 pub mod html;
+#[doc(hidden)]
+pub mod metadata;
 pub mod svg;
 
 /// Wrap a [`web_sys::CustomEvent`].
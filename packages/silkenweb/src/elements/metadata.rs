@@ -0,0 +1,153 @@
+//! Metadata about the typed element builders in [`html`][super::html] and
+//! [`svg`][super::svg].
+//!
+//! Each element's metadata is defined along with the element, by
+//! `dom_element!`, and collected here. It's used by `silkenweb-parse` to
+//! generate code that uses the typed builders.
+
+/// An element with a typed builder.
+pub struct Element {
+    /// The element name, as used in HTML.
+    pub name: &'static str,
+    /// The name of the function that constructs the element. Keywords are
+    /// raw identifiers, like `r#use`.
+    pub fn_name: &'static str,
+    /// Does the element implement `ParentElement`?
+    pub is_parent: bool,
+    /// The element specific attributes.
+    pub attributes: &'static [Attribute],
+}
+
+impl Element {
+    /// Find an element specific attribute by its HTML name.
+    pub fn attribute(&self, name: &str) -> Option<&'static Attribute> {
+        self.attributes.iter().find(|attr| attr.name == name)
+    }
+}
+
+/// An attribute method on a typed builder.
+pub struct Attribute {
+    /// The attribute name, as used in HTML.
+    pub name: &'static str,
+    /// The name of the builder method. Keywords are raw identifiers, like
+    /// `r#type`.
+    pub method: &'static str,
+    /// The type the builder method takes a value of, so callers know how to
+    /// convert an attribute value from HTML.
+    pub typ: AttributeType,
+}
+
+/// The type of an attribute method's value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AttributeType {
+    /// [`String`]. Any attribute value can be used.
+    String,
+    /// [`bool`]. The attribute is present when `true`.
+    Bool,
+    /// [`f32`].
+    F32,
+    /// [`f64`].
+    F64,
+    /// [`i32`].
+    I32,
+    /// [`i64`].
+    I64,
+    /// [`u32`].
+    U32,
+    /// [`u64`].
+    U64,
+    /// Any other type, such as [`Length`][super::svg::content_type::Length].
+    Other,
+}
+
+impl AttributeType {
+    /// The attribute type for a type, given its name as written in the element
+    /// definition.
+    pub const fn from_type_name(name: &str) -> Self {
+        const TYPES: [(&str, AttributeType); 8] = [
+            ("String", AttributeType::String),
+            ("bool", AttributeType::Bool),
+            ("f32", AttributeType::F32),
+            ("f64", AttributeType::F64),
+            ("i32", AttributeType::I32),
+            ("i64", AttributeType::I64),
+            ("u32", AttributeType::U32),
+            ("u64", AttributeType::U64),
+        ];
+
+        let mut index = 0;
+
+        while index < TYPES.len() {
+            if str_eq(TYPES[index].0, name) {
+                return TYPES[index].1;
+            }
+
+            index += 1;
+        }
+
+        Self::Other
+    }
+}
+
+/// The default for [`Element::is_parent`].
+///
+/// `parent_element!` defines an inherent `IS_PARENT` constant, which takes
+/// precedence over this.
+pub trait IsParent {
+    /// Does the element implement `ParentElement`?
+    const IS_PARENT: bool = false;
+}
+
+impl<T: ?Sized> IsParent for T {}
+
+macro_rules! elements {
+    ($module:ident: $($element:ident),* $(,)?) => {
+        &[$(<super::$module::$element>::METADATA),*]
+    };
+}
+
+/// The elements in [`html`][super::html].
+pub const HTML_ELEMENTS: &[Element] = elements!(html:
+    A, Abbr, B, Bdi, Bdo, Br, Cite, Code, Data, Dfn, Em, I, Kbd, Mark, Q, Rb,
+    Rp, Rt, Rtc, Ruby, S, Samp, Small, Span, Strong, Sub, Sup, Time, U, Var,
+    Wbr, Del, Ins, Address, Article, Aside, Footer, Header, H1, H2, H3, H4, H5,
+    H6, Hgroup, Main, Nav, Section, Embed, Iframe, Object, Param, Picture,
+    Source, Canvas, Noscript, Script, Area, Audio, Img, Map, Track, Video,
+    Details, Dialog, Menu, Summary, Blockquote, Dd, Div, Dl, Dt, Figcaption,
+    Figure, Hr, Li, Ol, P, Pre, Ul, Caption, Col, Colgroup, Table, Tbody, Td,
+    Tfoot, Th, Thead, Tr, Base, Head, Link, Meta, Style, Title, Button,
+    Datalist, Fieldset, Form, Input, Label, Legend, Meter, Optgroup, Option,
+    Output, Progress, Select, Textarea, Slot,
+);
+
+/// The elements in [`svg`][super::svg].
+pub const SVG_ELEMENTS: &[Element] = elements!(svg:
+    A, Animate, Circle, ClipPath, Defs, Desc, Ellipse, FeBlend, FeColorMatrix,
+    FeComponentTransfer, FeComposite, FeConvolveMatrix, FeDiffuseLighting,
+    FeDisplacementMap, FeDistantLight, FeFlood, FeFuncA, FeFuncB, FeFuncG,
+    FeFuncR, FeGaussianBlur, FeImage, FeMerge, FeMergeNode, FeMorphology,
+    FeOffset, FePointLight, FeSpecularLighting, FeSpotLight, FeTile,
+    FeTurbulence, Filter, ForeignObject, G, Line, Marker, Mask, Metadata, Mpath,
+    Path, Pattern, Polygon, Polyline, Rect, Script, Set, Stop, Style, Svg,
+    Switch, Symbol, Text, TextPath, Title, Tspan, Use, View,
+);
+
+const fn str_eq(lhs: &str, rhs: &str) -> bool {
+    let (lhs, rhs) = (lhs.as_bytes(), rhs.as_bytes());
+
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+
+    let mut index = 0;
+
+    while index < lhs.len() {
+        if lhs[index] != rhs[index] {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
@@ -0,0 +1,695 @@
+//! Typed form fields with validation and two-way binding.
+//!
+//! A [`Field`] holds a typed value, along with its validation state and
+//! whether it's been touched or edited. Fields are bound to form controls
+//! with:
+//!
+//! - [`BindValue::bind_value`] for text and number `<input>`s, `<textarea>`s
+//!   and single `<select>`s.
+//! - [`BindChecked::bind_checked`] for checkboxes.
+//! - [`BindChecked::bind_radio`] for radio button groups.
+//! - [`BindMultiple::bind_multiple`] for multi-selects.
+//!
+//! Changes to the field are reflected in the control, and user input updates
+//! the field. A [`Form`] groups fields together to check they're all valid
+//! before submitting.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use html::{button, div, input, Div};
+//! # use silkenweb::{
+//! #     form::{BindValue, Field, Form},
+//! #     prelude::*,
+//! # };
+//! struct SignUp {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let name = Field::new(String::new()).validator(|name: &String| {
+//!     if name.is_empty() {
+//!         Err("Please enter your name".to_string())
+//!     } else {
+//!         Ok(())
+//!     }
+//! });
+//! let age = Field::new(18u32);
+//! let sign_up = Form::new().field(&name).field(&age);
+//!
+//! # let app: Div =
+//! div()
+//!     .child(input().bind_value(&name))
+//!     .child(input().r#type("number").bind_value(&age))
+//!     .child(button().text("Sign Up").on_click(move |_, _| {
+//!         if let Some(sign_up) = sign_up.submit(|| SignUp {
+//!             name: name.get(),
+//!             age: age.get(),
+//!         }) {
+//!             // Send `sign_up` to the server
+//!         }
+//!     }));
+//! ```
+use std::{cell::RefCell, pin::Pin, rc::Rc};
+
+use futures::{future::LocalBoxFuture, Future, FutureExt};
+use futures_signals::{
+    signal::{Mutable, Signal, SignalExt},
+    signal_vec::{always, SignalVecExt},
+};
+use wasm_bindgen::JsCast;
+
+use crate::{
+    clone,
+    dom::Dom,
+    elements::{
+        html::{Input, Select, Textarea},
+        ElementEvents, HtmlElementEvents,
+    },
+    node::element::Element,
+    task::{TaskGroup, TaskHandle},
+    value::Sig,
+};
+
+/// The validation state of a [`Field`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Validity {
+    /// All validators passed.
+    Valid,
+    /// A validator failed, or the user input couldn't be parsed.
+    Invalid(String),
+    /// Asynchronous validators are still running.
+    Pending,
+}
+
+impl Validity {
+    /// Did all the validators pass?
+    ///
+    /// This is `false` while asynchronous validators are [`Pending`][Self::Pending].
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+
+    /// The error message, if the field is invalid.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Self::Invalid(error) => Some(error),
+            Self::Valid | Self::Pending => None,
+        }
+    }
+}
+
+/// Conversion between a value and the text of a form control.
+pub trait FormValue: Sized {
+    /// Convert `self` to the text of a form control.
+    fn to_form_value(&self) -> String;
+
+    /// Parse the text of a form control.
+    ///
+    /// The error is a message to show the user.
+    fn from_form_value(value: &str) -> Result<Self, String>;
+}
+
+impl FormValue for String {
+    fn to_form_value(&self) -> String {
+        self.clone()
+    }
+
+    fn from_form_value(value: &str) -> Result<Self, String> {
+        Ok(value.to_string())
+    }
+}
+
+/// An empty control is `None`.
+impl<T: FormValue> FormValue for Option<T> {
+    fn to_form_value(&self) -> String {
+        self.as_ref().map_or_else(String::new, T::to_form_value)
+    }
+
+    fn from_form_value(value: &str) -> Result<Self, String> {
+        if value.trim().is_empty() {
+            Ok(None)
+        } else {
+            T::from_form_value(value).map(Some)
+        }
+    }
+}
+
+macro_rules! number_form_values {
+    ($($typ:ty),*) => {
+        $(
+            impl FormValue for $typ {
+                fn to_form_value(&self) -> String {
+                    self.to_string()
+                }
+
+                fn from_form_value(value: &str) -> Result<Self, String> {
+                    value
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("\"{value}\" isn't a valid number"))
+                }
+            }
+        )*
+    }
+}
+
+number_form_values!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// A typed form field.
+///
+/// Cloning a `Field` gives another handle to the same field. Synchronous
+/// validators are run as soon as the value changes through the `Field` or a
+/// bound control, so [`Field::validity`] is always up to date. Changes made
+/// directly through [`Field::mutable`] are validated on the microtask queue.
+/// Asynchronous validators always run on the microtask queue.
+pub struct Field<T>(Rc<FieldState<T>>);
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Field<T> {
+    /// Create a field with an initial value.
+    pub fn new(initial: T) -> Self {
+        Self::from_mutable(Mutable::new(initial))
+    }
+
+    /// Create a field from a [`Mutable`].
+    ///
+    /// The current value of `value` is used as the initial value for
+    /// [`Field::dirty_signal`] and [`Field::reset`].
+    pub fn from_mutable(value: Mutable<T>) -> Self {
+        let state = Rc::new(FieldState {
+            initial: value.get_cloned(),
+            value,
+            touched: Mutable::new(false),
+            validity: Mutable::new(Validity::Valid),
+            validated: RefCell::new(None),
+            validators: RefCell::new(Vec::new()),
+            async_validators: RefCell::new(Vec::new()),
+            pending_validation: RefCell::new(None),
+            tasks: TaskGroup::new(),
+        });
+        let weak_state = Rc::downgrade(&state);
+
+        // Catch any changes made directly through the `Mutable`.
+        let _driver = state
+            .tasks
+            .spawn(state.value.signal_cloned().for_each(move |value| {
+                if let Some(state) = weak_state.upgrade() {
+                    if state.validated.borrow().as_ref() != Some(&value) {
+                        state.validate(value);
+                    }
+                }
+
+                async {}
+            }));
+
+        let field = Self(state);
+        field.validate();
+        field
+    }
+
+    /// Add a validator.
+    ///
+    /// Validators are run in the order they were added, and validation stops
+    /// at the first error.
+    pub fn validator(self, f: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        self.0.validators.borrow_mut().push(Box::new(f));
+        self.validate();
+        self
+    }
+
+    /// Add an asynchronous validator.
+    ///
+    /// Asynchronous validators are run after all synchronous validators have
+    /// passed. The field is [`Validity::Pending`] while they run, and any
+    /// validation that's in progress is cancelled when the value changes.
+    pub fn async_validator<Fut>(self, f: impl Fn(T) -> Fut + 'static) -> Self
+    where
+        Fut: Future<Output = Result<(), String>> + 'static,
+    {
+        self.0
+            .async_validators
+            .borrow_mut()
+            .push(Rc::new(move |value| f(value).boxed_local()));
+        self.validate();
+        self
+    }
+
+    /// Get the current value.
+    pub fn get(&self) -> T {
+        self.0.value.get_cloned()
+    }
+
+    /// Set the value.
+    pub fn set(&self, value: T) {
+        self.0.value.set(value);
+        self.validate();
+    }
+
+    /// The underlying [`Mutable`].
+    pub fn mutable(&self) -> &Mutable<T> {
+        &self.0.value
+    }
+
+    /// A signal of the value.
+    pub fn signal(&self) -> impl Signal<Item = T> + 'static {
+        self.0.value.signal_cloned()
+    }
+
+    /// Get the current validity.
+    pub fn validity(&self) -> Validity {
+        self.0.validity.get_cloned()
+    }
+
+    /// A signal of the validity.
+    pub fn validity_signal(&self) -> impl Signal<Item = Validity> + 'static {
+        self.0.validity.signal_cloned()
+    }
+
+    /// A signal of the error message, if the field is invalid.
+    pub fn error_signal(&self) -> impl Signal<Item = Option<String>> + 'static {
+        self.0
+            .validity
+            .signal_ref(|validity| validity.error().map(str::to_string))
+    }
+
+    /// Mark the field as touched.
+    ///
+    /// Bound controls mark their field as touched when they lose focus. This
+    /// is useful to only show errors once the user has finished with a field.
+    pub fn touch(&self) {
+        self.0.touched.set_neq(true);
+    }
+
+    /// A signal of whether the field has been touched.
+    pub fn touched_signal(&self) -> impl Signal<Item = bool> + 'static {
+        self.0.touched.signal()
+    }
+
+    /// A signal of whether the value is different to the initial value.
+    pub fn dirty_signal(&self) -> impl Signal<Item = bool> + 'static {
+        let initial = self.0.initial.clone();
+        self.0.value.signal_ref(move |value| *value != initial)
+    }
+
+    /// Run the validators again.
+    ///
+    /// Synchronous validators run immediately.
+    pub fn validate(&self) {
+        self.0.validate(self.get());
+    }
+
+    /// Set the value back to the initial value, and mark the field as
+    /// untouched.
+    pub fn reset(&self) {
+        self.set(self.0.initial.clone());
+        self.0.touched.set_neq(false);
+    }
+
+    fn set_from_control(&self, value: Result<T, String>) {
+        match value {
+            Ok(value) => self.set(value),
+            Err(error) => {
+                self.0.cancel_pending_validation();
+                self.0.validated.replace(None);
+                self.0.validity.set(Validity::Invalid(error));
+            }
+        }
+    }
+}
+
+impl<T: FormValue + Clone + PartialEq + 'static> Field<T> {
+    fn form_value_signal(&self) -> impl Signal<Item = String> + 'static {
+        self.0.value.signal_ref(T::to_form_value)
+    }
+}
+
+type Validator<T> = Box<dyn Fn(&T) -> Result<(), String>>;
+type AsyncValidator<T> = Rc<dyn Fn(T) -> LocalBoxFuture<'static, Result<(), String>>>;
+
+struct FieldState<T> {
+    value: Mutable<T>,
+    initial: T,
+    touched: Mutable<bool>,
+    validity: Mutable<Validity>,
+    validated: RefCell<Option<T>>,
+    validators: RefCell<Vec<Validator<T>>>,
+    async_validators: RefCell<Vec<AsyncValidator<T>>>,
+    pending_validation: RefCell<Option<TaskHandle<()>>>,
+    tasks: TaskGroup,
+}
+
+impl<T: Clone + 'static> FieldState<T> {
+    fn validate(&self, value: T) {
+        self.cancel_pending_validation();
+        self.validated.replace(Some(value.clone()));
+
+        let sync_result = self
+            .validators
+            .borrow()
+            .iter()
+            .try_for_each(|validator| validator(&value));
+
+        if let Err(error) = sync_result {
+            self.validity.set(Validity::Invalid(error));
+            return;
+        }
+
+        let async_validators = self.async_validators.borrow().clone();
+
+        if async_validators.is_empty() {
+            self.validity.set(Validity::Valid);
+            return;
+        }
+
+        self.validity.set(Validity::Pending);
+
+        let validation = self.tasks.spawn({
+            clone!(self.validity);
+
+            async move {
+                for validator in async_validators {
+                    if let Err(error) = validator(value.clone()).await {
+                        validity.set(Validity::Invalid(error));
+                        return;
+                    }
+                }
+
+                validity.set(Validity::Valid);
+            }
+        });
+
+        self.pending_validation.replace(Some(validation));
+    }
+
+    fn cancel_pending_validation(&self) {
+        if let Some(validation) = self.pending_validation.take() {
+            validation.abort();
+        }
+    }
+}
+
+/// Bind a [`Field`] to the value of a form control.
+pub trait BindValue<T>: Sized {
+    /// Bind `field` to the value of `self`.
+    ///
+    /// Changes to `field` update the control, and input to the control
+    /// updates `field`. If the input can't be parsed, the field will be
+    /// [`Validity::Invalid`] and its value won't change.
+    fn bind_value(self, field: &Field<T>) -> Self;
+}
+
+impl<D, T> BindValue<T> for Input<D>
+where
+    D: Dom,
+    T: FormValue + Clone + PartialEq + 'static,
+{
+    fn bind_value(self, field: &Field<T>) -> Self {
+        self.value(Sig(field.form_value_signal()))
+            .map_element_signal(field.form_value_signal(), |input, value| {
+                if input.value() != value {
+                    input.set_value(&value);
+                }
+            })
+            .on_input({
+                clone!(field);
+                move |_, input| field.set_from_control(T::from_form_value(&input.value()))
+            })
+            .on_blur({
+                clone!(field);
+                move |_, _| field.touch()
+            })
+    }
+}
+
+/// The value is only set in the browser, so when server side rendering, the
+/// initial text should be added as a child.
+impl<D, T> BindValue<T> for Textarea<D>
+where
+    D: Dom,
+    T: FormValue + Clone + PartialEq + 'static,
+{
+    fn bind_value(self, field: &Field<T>) -> Self {
+        self.map_element_signal(field.form_value_signal(), |textarea, value| {
+            if textarea.value() != value {
+                textarea.set_value(&value);
+            }
+        })
+        .on_input({
+            clone!(field);
+            move |_, textarea| field.set_from_control(T::from_form_value(&textarea.value()))
+        })
+        .on_blur({
+            clone!(field);
+            move |_, _| field.touch()
+        })
+    }
+}
+
+/// The selected option is only set in the browser, once the `<select>`'s
+/// children have been added.
+impl<D, T> BindValue<T> for Select<D>
+where
+    D: Dom,
+    T: FormValue + Clone + PartialEq + 'static,
+{
+    fn bind_value(self, field: &Field<T>) -> Self {
+        self.effect_signal(field.form_value_signal(), |select, value| {
+            if select.value() != value {
+                select.set_value(&value);
+            }
+        })
+        .on_change({
+            clone!(field);
+            move |_, select| field.set_from_control(T::from_form_value(&select.value()))
+        })
+        .on_blur({
+            clone!(field);
+            move |_, _| field.touch()
+        })
+    }
+}
+
+/// Bind a [`Field`] to the checked state of an `<input>`.
+pub trait BindChecked: Sized {
+    /// Bind `field` to a checkbox.
+    fn bind_checked(self, field: &Field<bool>) -> Self;
+
+    /// Bind `field` to a radio button that represents `value`.
+    ///
+    /// Bind each radio button in a group to the same field, with a different
+    /// `value`. The radio button is checked when the field equals `value`.
+    fn bind_radio<T>(self, field: &Field<T>, value: T) -> Self
+    where
+        T: Clone + PartialEq + 'static;
+}
+
+impl<D: Dom> BindChecked for Input<D> {
+    fn bind_checked(self, field: &Field<bool>) -> Self {
+        self.checked(Sig(field.signal()))
+            .map_element_signal(field.signal(), |input, checked| {
+                if input.checked() != checked {
+                    input.set_checked(checked);
+                }
+            })
+            .on_change({
+                clone!(field);
+                move |_, input| field.set(input.checked())
+            })
+            .on_blur({
+                clone!(field);
+                move |_, _| field.touch()
+            })
+    }
+
+    fn bind_radio<T>(self, field: &Field<T>, value: T) -> Self
+    where
+        T: Clone + PartialEq + 'static,
+    {
+        let is_checked = {
+            clone!(value);
+            move || {
+                clone!(value);
+                field.0.value.signal_ref(move |current| *current == value)
+            }
+        };
+
+        self.checked(Sig(is_checked()))
+            .map_element_signal(is_checked(), |input, checked| {
+                if input.checked() != checked {
+                    input.set_checked(checked);
+                }
+            })
+            .on_change({
+                clone!(field);
+                move |_, input| {
+                    if input.checked() {
+                        field.set(value.clone());
+                    }
+                }
+            })
+            .on_blur({
+                clone!(field);
+                move |_, _| field.touch()
+            })
+    }
+}
+
+/// Bind a [`Field`] to the selected options of a `<select multiple>`.
+pub trait BindMultiple<T>: Sized {
+    /// Bind `field` to the selected options of `self`.
+    ///
+    /// The selected options are only set in the browser, once the
+    /// `<select>`'s children have been added.
+    fn bind_multiple(self, field: &Field<Vec<T>>) -> Self;
+}
+
+impl<D, T> BindMultiple<T> for Select<D>
+where
+    D: Dom,
+    T: FormValue + Clone + PartialEq + 'static,
+{
+    fn bind_multiple(self, field: &Field<Vec<T>>) -> Self {
+        let selected_values = field
+            .0
+            .value
+            .signal_ref(|values| values.iter().map(T::to_form_value).collect::<Vec<String>>());
+
+        self.multiple(true)
+            .effect_signal(selected_values, |select, values| {
+                for option in options(select) {
+                    option.set_selected(values.contains(&option.value()));
+                }
+            })
+            .on_change({
+                clone!(field);
+                move |_, select| {
+                    field.set_from_control(
+                        options(&select)
+                            .filter(|option| option.selected())
+                            .map(|option| T::from_form_value(&option.value()))
+                            .collect(),
+                    )
+                }
+            })
+            .on_blur({
+                clone!(field);
+                move |_, _| field.touch()
+            })
+    }
+}
+
+fn options(
+    select: &web_sys::HtmlSelectElement,
+) -> impl Iterator<Item = web_sys::HtmlOptionElement> + '_ {
+    (0..select.length()).filter_map(|index| {
+        select
+            .item(index)
+            .and_then(|option| option.dyn_into::<web_sys::HtmlOptionElement>().ok())
+    })
+}
+
+/// A group of [`Field`]s.
+///
+/// # Example
+///
+/// See the [module-level documentation](self).
+#[derive(Clone, Default)]
+pub struct Form {
+    fields: Vec<Rc<dyn AnyField>>,
+}
+
+impl Form {
+    /// Create a form with no fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to the form.
+    pub fn field<T: Clone + PartialEq + 'static>(mut self, field: &Field<T>) -> Self {
+        self.fields.push(Rc::new(field.clone()));
+        self
+    }
+
+    /// Are all the fields currently valid?
+    pub fn is_valid(&self) -> bool {
+        self.fields.iter().all(|field| field.is_valid())
+    }
+
+    /// A signal of whether all the fields are valid.
+    pub fn valid_signal(&self) -> impl Signal<Item = bool> + 'static {
+        always(self.fields.clone())
+            .map_signal(|field| field.valid_signal())
+            .to_signal_map(|valid| valid.iter().all(|valid| *valid))
+    }
+
+    /// A signal of whether any of the fields are dirty.
+    pub fn dirty_signal(&self) -> impl Signal<Item = bool> + 'static {
+        always(self.fields.clone())
+            .map_signal(|field| field.dirty_signal())
+            .to_signal_map(|dirty| dirty.iter().any(|dirty| *dirty))
+    }
+
+    /// Mark all fields as touched.
+    pub fn touch_all(&self) {
+        for field in &self.fields {
+            field.touch();
+        }
+    }
+
+    /// Reset all fields.
+    pub fn reset(&self) {
+        for field in &self.fields {
+            field.reset();
+        }
+    }
+
+    /// Build the form output, if all fields are valid.
+    ///
+    /// All fields are marked as touched, so any errors can be shown. If all
+    /// fields are [`Validity::Valid`], `build` is called to gather the field
+    /// values for submission.
+    pub fn submit<R>(&self, build: impl FnOnce() -> R) -> Option<R> {
+        self.touch_all();
+        self.is_valid().then(build)
+    }
+}
+
+trait AnyField {
+    fn is_valid(&self) -> bool;
+
+    fn valid_signal(&self) -> Pin<Box<dyn Signal<Item = bool>>>;
+
+    fn dirty_signal(&self) -> Pin<Box<dyn Signal<Item = bool>>>;
+
+    fn touch(&self);
+
+    fn reset(&self);
+}
+
+impl<T: Clone + PartialEq + 'static> AnyField for Field<T> {
+    fn is_valid(&self) -> bool {
+        self.0.validity.lock_ref().is_valid()
+    }
+
+    fn valid_signal(&self) -> Pin<Box<dyn Signal<Item = bool>>> {
+        Box::pin(self.0.validity.signal_ref(Validity::is_valid))
+    }
+
+    fn dirty_signal(&self) -> Pin<Box<dyn Signal<Item = bool>>> {
+        Box::pin(Field::dirty_signal(self))
+    }
+
+    fn touch(&self) {
+        Field::touch(self)
+    }
+
+    fn reset(&self) {
+        Field::reset(self)
+    }
+}
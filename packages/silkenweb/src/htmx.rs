@@ -0,0 +1,436 @@
+//! Typed [htmx](https://htmx.org) attributes.
+//!
+//! [`HtmxElement`] is implemented for all HTML elements, and can be derived
+//! for custom elements.
+//!
+//! # Example
+//!
+//! ```
+//! # use std::{collections::BTreeMap, time::Duration};
+//! # use silkenweb::{
+//! #     dom::Dry,
+//! #     elements::html::{button, Button},
+//! #     htmx::{HtmxElement, Json, Swap, SwapStyle, Trigger},
+//! #     prelude::*,
+//! # };
+//! # fn main() -> serde_json::Result<()> {
+//! let elem: Button<Dry> = button()
+//!     .hx_post("/todo")
+//!     .hx_target("#todo-list")
+//!     .hx_swap(Swap::new(SwapStyle::BeforeEnd).settle(Duration::from_millis(100)))
+//!     .hx_trigger(Trigger::event("click").once())
+//!     .hx_vals(Json::new(BTreeMap::from([("priority", 1)]))?)
+//!     .text("Add");
+//!
+//! assert_eq!(
+//!     elem.freeze().to_string(),
+//!     r#"<button hx-post="/todo" hx-target="#todo-list" hx-swap="beforeend settle:100ms" hx-trigger="click once" hx-vals="{&quot;priority&quot;:1}">Add</button>"#
+//! );
+//! # Ok(())
+//! # }
+//! ```
+use std::{fmt, time::Duration};
+
+use serde::Serialize;
+
+use crate::{
+    attribute::{AsAttribute, Attribute},
+    node::element::Element,
+    value::Value,
+};
+
+macro_rules! htmx_attribute_doc {
+    ($element:expr, $name:expr) => {
+        concat!(
+            "The htmx [`",
+            $name,
+            "`](https://htmx.org/attributes/",
+            $name,
+            "/) attribute"
+        )
+    };
+}
+
+/// Methods for setting htmx attributes.
+pub trait HtmxElement: Element {
+    attributes![
+        [
+            attribute_parent = (),
+            attribute_doc_macro = htmx_attribute_doc
+        ]
+
+        /// Issue a `GET` request to the URL.
+        hx_get("hx-get"): String,
+        /// Issue a `POST` request to the URL.
+        hx_post("hx-post"): String,
+        /// Issue a `PUT` request to the URL.
+        hx_put("hx-put"): String,
+        /// Issue a `PATCH` request to the URL.
+        hx_patch("hx-patch"): String,
+        /// Issue a `DELETE` request to the URL.
+        hx_delete("hx-delete"): String,
+        /// The element to swap the response into, as an extended CSS selector.
+        hx_target("hx-target"): String,
+        /// How to swap the response into the target.
+        hx_swap("hx-swap"): Swap,
+        /// Swap this element in out of band, replacing or adding to the element
+        /// with the same `id`.
+        hx_swap_oob("hx-swap-oob"): SwapStyle,
+        /// The events that trigger a request.
+        hx_trigger("hx-trigger"): Trigger,
+        /// A CSS selector for the content to select from the response.
+        hx_select("hx-select"): String,
+        /// CSS selectors for content to swap out of band from the response.
+        hx_select_oob("hx-select-oob"): String,
+        /// `true` to push the request URL into the browser history, or the URL
+        /// to push.
+        hx_push_url("hx-push-url"): String,
+        /// `true` to replace the current URL in the browser history, or the URL
+        /// to replace it with.
+        hx_replace_url("hx-replace-url"): String,
+        /// A message to show in a confirmation dialog before issuing a request.
+        hx_confirm("hx-confirm"): String,
+        /// A CSS selector for the elements to add the `htmx-request` class to
+        /// during a request.
+        hx_indicator("hx-indicator"): String,
+        /// A CSS selector for additional elements to include values from.
+        hx_include("hx-include"): String,
+        /// Filter the parameters submitted with a request.
+        hx_params("hx-params"): String,
+        /// A CSS selector for elements to disable during a request.
+        hx_disabled_elt("hx-disabled-elt"): String,
+        /// How to synchronize requests between elements.
+        hx_sync("hx-sync"): String,
+        /// The htmx extensions to use for this element.
+        hx_ext("hx-ext"): String,
+        /// Change the request encoding, for example to `multipart/form-data`.
+        hx_encoding("hx-encoding"): String,
+        /// Disable htmx processing for this element and its children.
+        hx_disable("hx-disable"): bool,
+        /// Keep this element unchanged between requests.
+        hx_preserve("hx-preserve"): bool,
+        /// Values to add to the parameters of a request.
+        hx_vals("hx-vals"): Json,
+        /// Headers to add to a request.
+        hx_headers("hx-headers"): Json,
+    ];
+}
+
+/// A value serialized as JSON, for [`HtmxElement::hx_vals`] and
+/// [`HtmxElement::hx_headers`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Json(String);
+
+impl Json {
+    /// Serialize `value` as JSON.
+    pub fn new(value: impl Serialize) -> serde_json::Result<Self> {
+        serde_json::to_string(&value).map(Self)
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// How the response is swapped into the target.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SwapStyle {
+    InnerHtml,
+    OuterHtml,
+    BeforeBegin,
+    AfterBegin,
+    BeforeEnd,
+    AfterEnd,
+    Delete,
+    None,
+}
+
+impl fmt::Display for SwapStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InnerHtml => "innerHTML",
+            Self::OuterHtml => "outerHTML",
+            Self::BeforeBegin => "beforebegin",
+            Self::AfterBegin => "afterbegin",
+            Self::BeforeEnd => "beforeend",
+            Self::AfterEnd => "afterend",
+            Self::Delete => "delete",
+            Self::None => "none",
+        })
+    }
+}
+
+/// A scroll position for [`Swap::scroll`] and [`Swap::show`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScrollPosition {
+    Top,
+    Bottom,
+}
+
+impl fmt::Display for ScrollPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+        })
+    }
+}
+
+/// A swap style, with modifiers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Swap {
+    style: SwapStyle,
+    modifiers: Vec<String>,
+}
+
+impl Swap {
+    pub fn new(style: SwapStyle) -> Self {
+        Self {
+            style,
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// Delay between receiving the response and swapping it in.
+    pub fn swap(self, delay: Duration) -> Self {
+        self.modifier(format!("swap:{}", time(delay)))
+    }
+
+    /// Delay between swapping the response in and settling.
+    pub fn settle(self, delay: Duration) -> Self {
+        self.modifier(format!("settle:{}", time(delay)))
+    }
+
+    /// Use the View Transitions API for the swap.
+    pub fn transition(self, transition: bool) -> Self {
+        self.modifier(format!("transition:{transition}"))
+    }
+
+    /// Don't update the page title from the response.
+    pub fn ignore_title(self, ignore: bool) -> Self {
+        self.modifier(format!("ignoreTitle:{ignore}"))
+    }
+
+    /// Scroll the target, or the element matching `selector`, to `position`.
+    pub fn scroll(self, position: ScrollPosition, selector: Option<&str>) -> Self {
+        self.modifier(format!("scroll:{}", scroll_target(position, selector)))
+    }
+
+    /// Scroll the target, or the element matching `selector`, into view at
+    /// `position`.
+    pub fn show(self, position: ScrollPosition, selector: Option<&str>) -> Self {
+        self.modifier(format!("show:{}", scroll_target(position, selector)))
+    }
+
+    /// Scroll to the focused element after the swap.
+    pub fn focus_scroll(self, scroll: bool) -> Self {
+        self.modifier(format!("focus-scroll:{scroll}"))
+    }
+
+    fn modifier(mut self, modifier: String) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+}
+
+impl From<SwapStyle> for Swap {
+    fn from(style: SwapStyle) -> Self {
+        Self::new(style)
+    }
+}
+
+impl fmt::Display for Swap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.style.fmt(f)?;
+
+        for modifier in &self.modifiers {
+            write!(f, " {modifier}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Which requests to keep when requests are queued, for
+/// [`Trigger::queue`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Queue {
+    First,
+    Last,
+    All,
+    None,
+}
+
+impl fmt::Display for Queue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::First => "first",
+            Self::Last => "last",
+            Self::All => "all",
+            Self::None => "none",
+        })
+    }
+}
+
+/// The events that trigger a request.
+///
+/// Modifiers apply to the most recently added event.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use silkenweb::htmx::Trigger;
+/// let trigger = Trigger::event("keyup")
+///     .changed()
+///     .delay(Duration::from_millis(500))
+///     .or(Trigger::event("search"));
+///
+/// assert_eq!(trigger.to_string(), "keyup changed delay:500ms, search");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trigger(Vec<TriggerSpec>);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct TriggerSpec {
+    event: String,
+    filter: Option<String>,
+    modifiers: Vec<String>,
+}
+
+impl Trigger {
+    /// Trigger on a DOM event, for example `"click"`.
+    pub fn event(name: impl Into<String>) -> Self {
+        Self(vec![TriggerSpec {
+            event: name.into(),
+            filter: None,
+            modifiers: Vec::new(),
+        }])
+    }
+
+    /// Poll every `interval`.
+    pub fn every(interval: Duration) -> Self {
+        Self::event(format!("every {}", time(interval)))
+    }
+
+    /// Also trigger on the events in `other`.
+    pub fn or(mut self, other: Self) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    /// Only trigger if the JavaScript expression `filter` is true.
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.last().filter = Some(filter.into());
+        self
+    }
+
+    /// Only trigger once.
+    pub fn once(self) -> Self {
+        self.modifier("once".to_string())
+    }
+
+    /// Only trigger if the value of the element has changed.
+    pub fn changed(self) -> Self {
+        self.modifier("changed".to_string())
+    }
+
+    /// Wait for `delay` before triggering, restarting the delay if the event
+    /// occurs again.
+    pub fn delay(self, delay: Duration) -> Self {
+        self.modifier(format!("delay:{}", time(delay)))
+    }
+
+    /// Trigger at most once every `interval`.
+    pub fn throttle(self, interval: Duration) -> Self {
+        self.modifier(format!("throttle:{}", time(interval)))
+    }
+
+    /// Listen for the event on the elements matching `selector`.
+    pub fn from(self, selector: &str) -> Self {
+        self.modifier(format!("from:{selector}"))
+    }
+
+    /// Only trigger if the event target matches `selector`.
+    pub fn target(self, selector: &str) -> Self {
+        self.modifier(format!("target:{selector}"))
+    }
+
+    /// Stop the event propagating to parent elements.
+    pub fn consume(self) -> Self {
+        self.modifier("consume".to_string())
+    }
+
+    /// How to queue events that occur while a request is in flight.
+    pub fn queue(self, queue: Queue) -> Self {
+        self.modifier(format!("queue:{queue}"))
+    }
+
+    fn modifier(mut self, modifier: String) -> Self {
+        self.last().modifiers.push(modifier);
+        self
+    }
+
+    fn last(&mut self) -> &mut TriggerSpec {
+        self.0
+            .last_mut()
+            .expect("A trigger always has at least one event")
+    }
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, spec) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            f.write_str(&spec.event)?;
+
+            if let Some(filter) = &spec.filter {
+                write!(f, "[{filter}]")?;
+            }
+
+            for modifier in &spec.modifiers {
+                write!(f, " {modifier}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! display_attributes {
+    ($($typ:ty),* $(,)?) => {
+        $(
+            impl Attribute for $typ {
+                type Text<'a> = String;
+
+                fn text(&self) -> Option<Self::Text<'_>> {
+                    Some(self.to_string())
+                }
+            }
+
+            impl AsAttribute<$typ> for $typ {}
+
+            impl Value for $typ {}
+        )*
+    };
+}
+
+display_attributes!(SwapStyle, Swap, Trigger, Json);
+
+impl AsAttribute<Swap> for SwapStyle {}
+
+fn time(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+fn scroll_target(position: ScrollPosition, selector: Option<&str>) -> String {
+    match selector {
+        Some(selector) => format!("{selector}:{position}"),
+        None => position.to_string(),
+    }
+}
@@ -57,6 +57,16 @@
 //! Enable CSS transpilation for [`css!`]. This can significantly increase build
 //! time, so is presented as an opt-in feature.
 //!
+//! ## `worker`
+//!
+//! Run tasks in Web Workers, with typed channels, using `task::worker`.
+//! Messages are encoded with `serde_json`.
+//!
+//! ## `htmx`
+//!
+//! Typed [htmx](https://htmx.org) attributes for HTML elements, in the `htmx`
+//! module.
+//!
 //! # Learning
 //!
 //! There's extensive documentation on each module in this crate, along with
@@ -82,18 +92,31 @@ use node::element::{Const, GenericElement};
 use silkenweb_base::document as base_document;
 /// Define `&str` constants for each class in a CSS file.
 ///
-/// This defines 2 modules:
+/// This defines these modules:
 ///
 /// - `mod class` with constants or functions (depending on `auto_mount`) for
 ///   each CSS class. For a CSS class called `my-css-class`, a constant called
-///   `MY_CSS_CLASS` or a function called `my_css_class` will be defined.
+///   `MY_CSS_CLASS` or a function called `my_css_class` will be defined. With
+///   `typed`, it also has a `Class` type, with an associated constant or
+///   function for each class. See [`typed_class`] for details.
+/// - `mod var` with constants or functions for each CSS custom property name.
+/// - `mod property` with a typed [`CustomProperty`] for each custom property
+///   registered with an `@property` rule. See [`custom_property`] for details.
 /// - `mod stylesheet` with:
 ///     - An `fn text() -> &'static str` that gets the content of the
 ///       stylesheet.
-///     - `fn mount()` and `fn mount_dom<D: Document>()` that lazily call
-///       [`DefaultDom::mount_in_head`] or `D:mount_in_head` once, respectively.
-///       This ensures the stylesheet is in the head. Any subsequent calls to
-///       either function will have no effect.
+///     - `fn mount()` and `fn mount_dom<D: Document>()` that call
+///       [`DefaultDom::mount_stylesheet`] or `D::mount_stylesheet`,
+///       respectively. This ensures the stylesheet is in the head. Any
+///       subsequent calls to either function will have no effect. During
+///       server side rendering, this records the stylesheet as used by the
+///       current render, so [`Document::head_inner_html`] only contains the
+///       stylesheets the page needs.
+///     - An `ID` constant, which uniquely identifies the stylesheet.
+///     - For `scoped` stylesheets, a `SCOPE` constant with the scope attribute
+///       name, and `fn scoped<T>(f: impl FnOnce() -> T) -> T`, which mounts
+///       the stylesheet and adds the scope attribute to elements created by
+///       `f`.
 ///
 /// The macro takes two forms. Firstly it can take a single string literal which
 /// is the path to the CSS/SCSS/SASS file. The path is relative to the
@@ -114,6 +137,8 @@ use silkenweb_base::document as base_document;
 ///     include_prefixes = ["included-"],
 ///     exclude_prefixes = ["excluded-"],
 ///     auto_mount,
+///     scoped,
+///     typed,
 #[cfg_attr(
     feature = "css-transpile",
     doc = r#"
@@ -158,6 +183,15 @@ use silkenweb_base::document as base_document;
 ///   `exclude_prefixes` takes precedence over `include_prefixes`.
 /// - `auto_mount`: Generate a function for each CSS class that will call
 ///   `stylesheet::mount` before returning the class name.
+/// - `scoped`: Scope the stylesheet to a component, without using shadow DOM.
+///   Each selector has an attribute selector added, and `stylesheet::scoped(f)`
+///   will add the attribute to all elements created by `f`. See
+///   [`with_style_scope`] for details.
+/// - `typed`: Generate a `class::Class` type, for use with
+///   [`TypedClassElement`].
+/// - `bundled`: The stylesheet is in a CSS bundle that's linked from the page,
+///   so `stylesheet::mount` does nothing. See [Bundling
+///   Stylesheets](#bundling-stylesheets).
 /// - `validate`: validate the CSS. Requires crate feature `css-transpile`.
 /// - `transpile`: transpile the CSS with [lightningcss]. Requires crate feature `css-transpile`.
 ///
@@ -170,7 +204,12 @@ use silkenweb_base::document as base_document;
 /// - `pretty`: Pretty print the final output. This is the default unless minify
 ///   is specified.
 /// - `modules`: Enable [CSS Modules] to locally scope class identifiers, via
-///   [lightningcss]. Composition is unsupported.
+///   [lightningcss]. Classes that use `composes` are defined as a
+///   `&[&str]` of all their class names, for use with
+///   [`Element::classes`][crate::node::element::Element::classes]. They can
+///   compose classes from the same file, global classes, or classes from
+///   other files. Another file's stylesheet must be mounted separately, for
+///   example with its own `css!` invocation.
 /// - `nesting`: Allow CSS nesting.
 /// - `browsers` is a comma seperated list of the minimum supported browser
 ///   versions. This will add vendor prefixes to the CSS from `stylesheet()`.
@@ -178,6 +217,16 @@ use silkenweb_base::document as base_document;
 ///   patch versions. For example, to support firefox 110  + and chrome 111+,
 ///   use `browsers =( firefox = (110, 0, 0), chrome = (111, 0, 0) )`.
 ///
+/// # Bundling Stylesheets
+///
+/// `silkenweb_css::bundle::Bundle` combines stylesheets into a single CSS
+/// file with a content hash in its name, which should be linked from the page.
+/// It's usually built from a build script, using the same files and options as
+/// the `css!` invocations. Mark those invocations `bundled`, so they aren't
+/// mounted at runtime. `stylesheet::text` still returns the stylesheet, but it
+/// isn't linked into the binary unless it's used. See `silkenweb_css::bundle`
+/// for an example.
+///
 /// # Examples
 ///
 /// Define private constants for all CSS classes:
@@ -231,9 +280,97 @@ use silkenweb_base::document as base_document;
 /// ```
 ///
 /// [lightningcss]: https://lightningcss.dev/
-/// [`DefaultDom::mount_in_head`]: crate::dom::DefaultDom::mount_in_head
+/// [`DefaultDom::mount_stylesheet`]: crate::dom::DefaultDom::mount_stylesheet
+/// [`CustomProperty`]: crate::custom_property::CustomProperty
+/// [`with_style_scope`]: crate::node::element::with_style_scope
+/// [`TypedClassElement`]: crate::typed_class::TypedClassElement
 /// [CSS Modules]: https://github.com/css-modules/css-modules
 pub use silkenweb_macros::css;
+/// Generate custom element types from a [Custom Elements Manifest].
+///
+/// This reads a `custom-elements.json` file, and defines an element for each
+/// custom element declaration, as if with [`custom_html_element`]. The path is
+/// relative to the `$CARGO_MANIFEST_DIR` environment variable.
+///
+/// For each element, the macro generates:
+///
+/// - A method for each attribute. `boolean` attributes are `bool`, `number`
+///   attributes are `f64`, and anything else is a `String`.
+/// - A `set_<property>` method for each public field with a `boolean`,
+///   `number` or `string` type. These take a value, or a [`Sig`][value::Sig]
+///   to set the property each time the signal changes.
+/// - An `on_<event>` method for each event, taking a [`CustomEvent`].
+/// - If there's a default slot, `child` and `text` methods for it.
+/// - A `<slot>_slot` method for each named slot.
+///
+/// Only some of the elements can be generated by specifying the `elements`
+/// parameter.
+///
+/// # Example
+///
+/// ```
+/// # use silkenweb::{custom_elements_manifest, prelude::*};
+/// custom_elements_manifest!("tests/custom-elements.json");
+///
+/// // Or with named parameters:
+/// mod just_my_button {
+///     # use silkenweb::custom_elements_manifest;
+///     custom_elements_manifest!(
+///         path = "tests/custom-elements.json",
+///         elements = ["my-button"]
+///     );
+/// }
+///
+/// let button: MyButton = my_button()
+///     .label("Press me")
+///     .on_my_press(|_event, _button| {});
+/// ```
+///
+/// [Custom Elements Manifest]: https://github.com/webcomponents/custom-elements-manifest
+/// [`CustomEvent`]: crate::elements::CustomEvent
+pub use silkenweb_macros::custom_elements_manifest;
+/// Build elements with an HTML-like syntax.
+///
+/// The template expands to the typed builders in [`elements::html`], or
+/// [`elements::svg`] inside an `<svg>` element. The template must have a
+/// single root element, and the expression has the element's type, for
+/// example [`Div`][elements::html::Div].
+///
+/// - `<tag attribute=value>children</tag>`, or `<tag attribute=value />`,
+///   calls `tag()`. Unknown tags are reported at the tag.
+/// - `attribute="literal"` or `attribute={expr}` calls `.attribute(value)`.
+///   `-` in names is replaced by `_`, so `aria-label` calls `.aria_label`.
+///   Unknown attributes are reported at the attribute name.
+/// - `attribute` with no value calls `.attribute(true)`.
+/// - `class="a b"` adds each class. `data-*` attributes are set with
+///   [`Element::attribute`][node::element::Element::attribute].
+/// - `on:event={handler}` calls `.on_event(handler)`.
+/// - `"text"` adds a text child.
+/// - `{expr}` adds a child. Strings, and [`Sig`][value::Sig] signals of
+///   strings, are added as text. Nodes, and `Sig` signals of nodes, are added
+///   with [`ParentElement::child`][node::element::ParentElement::child].
+/// - `{..iter}` adds each node in `iter` as a child.
+///
+/// # Example
+///
+/// ```
+/// # use html::Div;
+/// # use silkenweb::{html, prelude::*};
+/// let count = Mutable::new(0);
+/// let count_text = Sig(count.signal().map(|i| i.to_string()));
+/// let items = ["one", "two"];
+///
+/// let app: Div = html! {
+///     <div class="counter" id="app">
+///         <button on:click={move |_, _| count.set(count.get() + 1)}>"+"</button>
+///         <p>"Count: " {count_text}</p>
+///         <ul>
+///             {..items.iter().map(|item| html!(<li>{*item}</li>))}
+///         </ul>
+///     </div>
+/// };
+/// ```
+pub use silkenweb_macros::html;
 /// Derive the traits needed for a blanket implmenetation of [`ChildElement`].
 ///
 /// This only works for structs. It will defer to one field for the
@@ -300,6 +437,9 @@ pub use silkenweb_macros::Element;
 pub use silkenweb_macros::{
     cfg_browser, AriaElement, ElementEvents, HtmlElement, HtmlElementEvents, Value,
 };
+#[cfg(feature = "htmx")]
+#[doc(inline)]
+pub use silkenweb_macros::HtmxElement;
 pub use wasm_rs_dbg::dbg;
 
 #[doc(hidden)]
@@ -310,9 +450,14 @@ mod event;
 
 pub mod animation;
 pub mod attribute;
+pub mod custom_element;
+pub mod custom_property;
 pub mod document;
 pub mod dom;
 pub mod elements;
+pub mod form;
+#[cfg(feature = "htmx")]
+pub mod htmx;
 pub mod hydration;
 pub mod node;
 pub mod property;
@@ -320,6 +465,7 @@ pub mod router;
 pub mod storage;
 pub mod task;
 pub mod time;
+pub mod typed_class;
 pub mod window;
 
 /// Commonly used imports, all in one place.
@@ -1,10 +1,92 @@
 pub use futures_signals::{signal::Signal, signal_vec::SignalVec};
+pub use js_sys;
 pub use paste::paste;
 pub use silkenweb_macros::rust_to_html_ident;
 pub use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 pub use web_sys;
 
 pub use crate::intern_str;
+use crate::{
+    dom::Dom,
+    node::{element::ParentElement, ChildNode},
+    value::Sig,
+};
+
+/// A `{expr}` child in [`html!`][crate::html].
+///
+/// Nodes are added with [`ParentElement::child`]. Strings, and signals of
+/// strings, are added with [`ParentElement::text`]. Signals of nodes are added
+/// with [`ParentElement::child`].
+pub trait HtmlChild<D: Dom> {
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P;
+}
+
+impl<D: Dom, T: ChildNode<D>> HtmlChild<D> for T {
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P {
+        parent.child(self)
+    }
+}
+
+impl<D: Dom> HtmlChild<D> for &str {
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P {
+        parent.text(self)
+    }
+}
+
+impl<D: Dom> HtmlChild<D> for String {
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P {
+        parent.text(self)
+    }
+}
+
+impl<D, S> HtmlChild<D> for Sig<S>
+where
+    D: Dom,
+    S: Signal + 'static,
+    S::Item: HtmlSignalChild<D>,
+{
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P {
+        S::Item::add_signal_to(self.0, parent)
+    }
+}
+
+/// The item of a [`Sig`] signal `{expr}` child in [`html!`][crate::html].
+pub trait HtmlSignalChild<D: Dom>: Sized {
+    fn add_signal_to<P, S>(signal: S, parent: P) -> P
+    where
+        P: ParentElement<D>,
+        S: Signal<Item = Self> + 'static;
+}
+
+impl<D: Dom, T: ChildNode<D>> HtmlSignalChild<D> for T {
+    fn add_signal_to<P, S>(signal: S, parent: P) -> P
+    where
+        P: ParentElement<D>,
+        S: Signal<Item = Self> + 'static,
+    {
+        parent.child(Sig(signal))
+    }
+}
+
+impl<D: Dom> HtmlSignalChild<D> for &'static str {
+    fn add_signal_to<P, S>(signal: S, parent: P) -> P
+    where
+        P: ParentElement<D>,
+        S: Signal<Item = Self> + 'static,
+    {
+        parent.text(Sig(signal))
+    }
+}
+
+impl<D: Dom> HtmlSignalChild<D> for String {
+    fn add_signal_to<P, S>(signal: S, parent: P) -> P
+    where
+        P: ParentElement<D>,
+        S: Signal<Item = Self> + 'static,
+    {
+        parent.text(Sig(signal))
+    }
+}
 
 /// Define a custom html element.
 ///
@@ -14,8 +96,8 @@ pub use crate::intern_str;
 /// identifiers have their `r#` prefix stripped.
 ///
 /// The html identifier can be explicitly specified in brackets after the
-/// element or attribute name. See `my_explicitly_named_attribute` in the
-/// example.
+/// element, attribute or custom event name. See
+/// `my_explicitly_named_attribute` in the example.
 ///
 /// # Example
 ///
@@ -47,6 +129,48 @@ pub use crate::intern_str;
 /// ```
 #[macro_export]
 macro_rules! custom_html_element {
+    (
+        $(#[$elem_meta:meta])*
+        $name:ident $( ($text_name: literal) )? = {
+            $($tail:tt)*
+        }
+    ) => {
+        $crate::html_dom_element!(
+            $(#[$elem_meta])*
+            $name $( ($text_name) )? = {
+                $($tail)*
+            }
+        );
+    }
+}
+
+macro_rules! html_element {
+    (
+        $(#[$elem_meta:meta])*
+        $name:ident $( ($text_name: literal) )? = {
+            $($tail:tt)*
+        }
+    ) => {
+        $crate::html_dom_element!(
+            $(#[$elem_meta])*
+            $name $( ($text_name) )? = {
+                doc_macro = html_element_doc;
+                attribute_doc_macro = html_attribute_doc;
+                $($tail)*
+            }
+        );
+    }
+}
+
+/// Define an HTML element, with the common HTML attributes and events.
+///
+/// This includes the htmx attributes if the `htmx` feature is enabled. It's a
+/// separate macro so the features of this crate decide, rather than the
+/// features of the crate using `custom_html_element!`.
+#[cfg(feature = "htmx")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! html_dom_element {
     (
         $(#[$elem_meta:meta])*
         $name:ident $( ($text_name: literal) )? = {
@@ -56,7 +180,7 @@ macro_rules! custom_html_element {
         $crate::dom_element!(
             $(#[$elem_meta])*
             $name $( ($text_name) )? = {
-                common_attributes = [$crate::elements::HtmlElement, $crate::elements::AriaElement];
+                common_attributes = [$crate::elements::HtmlElement, $crate::elements::AriaElement, $crate::htmx::HtmxElement];
                 common_events = [$crate::elements::HtmlElementEvents];
                 namespace = &$crate::node::element::Namespace::Html;
                 $($tail)*
@@ -65,7 +189,11 @@ macro_rules! custom_html_element {
     }
 }
 
-macro_rules! html_element {
+/// Define an HTML element, with the common HTML attributes and events.
+#[cfg(not(feature = "htmx"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! html_dom_element {
     (
         $(#[$elem_meta:meta])*
         $name:ident $( ($text_name: literal) )? = {
@@ -78,8 +206,6 @@ macro_rules! html_element {
                 common_attributes = [$crate::elements::HtmlElement, $crate::elements::AriaElement];
                 common_events = [$crate::elements::HtmlElementEvents];
                 namespace = &$crate::node::element::Namespace::Html;
-                doc_macro = html_element_doc;
-                attribute_doc_macro = html_attribute_doc;
                 $($tail)*
             }
         );
@@ -187,7 +313,7 @@ macro_rules! dom_element {
 
             $(custom_events { $(
                     $(#[$custom_event_meta:meta])*
-                    $custom_event:ident: $custom_event_type:ty
+                    $custom_event:ident $( ($text_custom_event:literal) )?: $custom_event_type:ty
                 ),* $(,)?
             };)?
 
@@ -229,6 +355,21 @@ macro_rules! dom_element {
                 $camel_name(self.0.freeze())
             }
 
+            #[doc(hidden)]
+            pub const METADATA: $crate::elements::metadata::Element =
+                $crate::elements::metadata::Element {
+                    name: $text_name,
+                    fn_name: ::std::stringify!($snake_name),
+                    is_parent: {
+                        #[allow(unused_imports)]
+                        use $crate::elements::metadata::IsParent as _;
+                        Self::IS_PARENT
+                    },
+                    attributes: &[
+                        $($($crate::attribute_metadata!($attr $( ($text_attr) )?: $typ),)*)?
+                    ],
+                };
+
             $crate::attributes![
                 $([
                         attribute_parent = $text_name,
@@ -250,7 +391,7 @@ macro_rules! dom_element {
                 $elem_type {
                     $(
                         $(#[$custom_event_meta])*
-                        $custom_event: $custom_event_type
+                        $custom_event $( ($text_custom_event) )?: $custom_event_type
                     ),*
                 }
             ); )?
@@ -374,6 +515,10 @@ macro_rules! dom_element {
                 Self(self.0.spawn_future(future))
             }
 
+            fn task_group(self, group: $crate::task::TaskGroup) -> Self {
+                Self(self.0.task_group(group))
+            }
+
             fn on(
                 self,
                 name: &'static str,
@@ -466,6 +611,11 @@ macro_rules! dom_element {
 #[macro_export]
 macro_rules! parent_element {
     ($name:ident) => {$crate::macros::paste!{
+        impl<Dom: $crate::dom::Dom> [< $name:camel >] <Dom> {
+            #[doc(hidden)]
+            pub const IS_PARENT: bool = true;
+        }
+
         impl<Dom: $crate::dom::Dom> $crate::node::element::ParentElement<Dom>
         for [< $name:camel >] <Dom>
         {
@@ -575,7 +725,7 @@ macro_rules! custom_events {
     ($elem_type:ty {
         $(
             $(#[$event_meta:meta])*
-            $name:ident: $event_type:ty
+            $name:ident $( ($text_name:literal) )?: $event_type:ty
         ),* $(,)?
     }) => { $crate::macros::paste!{
         $(
@@ -586,7 +736,7 @@ macro_rules! custom_events {
             ) -> Self {
                 $crate::node::element::Element::on(
                     self,
-                    $crate::text_name_intern!($name),
+                    $crate::text_name_intern!($name $( ($text_name) )?),
                     move |js_ev| {
                         use $crate::macros::JsCast;
                         // I *think* it's safe to assume event and event.current_target aren't null
@@ -696,6 +846,21 @@ macro_rules! attribute {
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! attribute_metadata {
+    ($attr:ident : $typ:ty) => {
+        $crate::attribute_metadata!($attr ($crate::macros::rust_to_html_ident!($attr)): $typ)
+    };
+    ($attr:ident ($text_attr:expr): $typ:ty) => {
+        $crate::elements::metadata::Attribute {
+            name: $text_attr,
+            method: ::std::stringify!($attr),
+            typ: $crate::elements::metadata::AttributeType::from_type_name(::std::stringify!($typ)),
+        }
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! properties {
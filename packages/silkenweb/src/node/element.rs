@@ -2,10 +2,13 @@
 #[cfg(debug_assertions)]
 use std::collections::HashSet;
 use std::{
-    self, fmt,
+    self,
+    cell::Cell,
+    fmt,
     future::Future,
     marker::PhantomData,
     pin::{pin, Pin},
+    sync::atomic::{self, AtomicUsize},
 };
 
 use discard::DiscardOnDrop;
@@ -33,11 +36,53 @@ use crate::{
     hydration::HydrationStats,
     intern_str,
     node::text,
-    task,
+    task::{self, TaskGroup},
 };
 
 pub(crate) mod child_vec;
 
+/// Add the attribute `scope` to every element created while running `f`.
+///
+/// This is used by scoped stylesheets from [`css!`], to apply the stylesheet's
+/// scope to the elements in a component. Only the innermost scope is applied.
+/// Elements that are created later, for example in a `map` over a signal,
+/// will not be scoped unless their closure also calls `with_style_scope`.
+///
+/// [`css!`]: crate::css
+pub fn with_style_scope<T>(scope: &'static str, f: impl FnOnce() -> T) -> T {
+    struct RestoreScope(Option<&'static str>);
+
+    impl Drop for RestoreScope {
+        fn drop(&mut self) {
+            STYLE_SCOPE.with(|current| current.set(self.0));
+            ACTIVE_STYLE_SCOPES.fetch_sub(1, atomic::Ordering::Relaxed);
+        }
+    }
+
+    ACTIVE_STYLE_SCOPES.fetch_add(1, atomic::Ordering::Relaxed);
+    let _restore_scope = RestoreScope(STYLE_SCOPE.with(|current| current.replace(Some(scope))));
+
+    f()
+}
+
+/// The innermost style scope, if there is one.
+///
+/// Unscoped code, which is most code, shouldn't pay for style scopes, so we
+/// only look at the thread local if some thread has an active scope.
+fn current_style_scope() -> Option<&'static str> {
+    if ACTIVE_STYLE_SCOPES.load(atomic::Ordering::Relaxed) == 0 {
+        None
+    } else {
+        STYLE_SCOPE.with(Cell::get)
+    }
+}
+
+static ACTIVE_STYLE_SCOPES: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static STYLE_SCOPE: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
 /// A generic HTML element.
 ///
 /// Where available, specific DOM elements from [`crate::elements::html`] should
@@ -58,7 +103,13 @@ pub struct GenericElement<D: Dom = DefaultDom, Mutability = Mut> {
 impl<D: Dom> GenericElement<D> {
     /// Construct an element with type `tag` in `namespace`.
     pub fn new(namespace: &Namespace, tag: &str) -> Self {
-        Self::from_dom(D::Element::new(namespace, tag), 0)
+        let elem = Self::from_dom(D::Element::new(namespace, tag), 0);
+
+        if let Some(scope) = current_style_scope() {
+            elem.attribute(scope, "")
+        } else {
+            elem
+        }
     }
 
     /// Make this element immutable.
@@ -479,6 +530,11 @@ impl<D: Dom> Element for GenericElement<D> {
         self
     }
 
+    fn task_group(mut self, group: TaskGroup) -> Self {
+        self.resources.push(Resource::Any(Box::new(group)));
+        self
+    }
+
     fn on(mut self, name: &'static str, f: impl FnMut(JsValue) + 'static) -> Self {
         self.element.on(name, f, &mut self.events);
         self
@@ -731,6 +787,27 @@ pub trait Element: Sized {
     /// The future will be dropped when this element is dropped.
     fn spawn_future(self, future: impl Future<Output = ()> + 'static) -> Self;
 
+    /// Keep a [`TaskGroup`] alive for as long as this element.
+    ///
+    /// The element holds a handle to `group`, so the group's tasks are aborted
+    /// when the element and all other handles to the group are dropped. Unlike
+    /// [`Element::spawn_future`], tasks in the group can be spawned after the
+    /// element is built, aborted, or awaited.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use silkenweb::{prelude::*, task::TaskGroup};
+    /// # use html::{div, Div};
+    /// let tasks = TaskGroup::new();
+    /// let app: Div = div().task_group(tasks.clone());
+    ///
+    /// let _handle = tasks.spawn(async {
+    ///     // This will be aborted when `app` and `tasks` are dropped.
+    /// });
+    /// ```
+    fn task_group(self, group: TaskGroup) -> Self;
+
     /// Register an event handler.
     ///
     /// `name` is the name of the event. See the [MDN Events] page for a list.
@@ -24,14 +24,21 @@ pub(crate) mod local;
 pub use arch::scope;
 /// Synchronous version of [`scope`].
 pub use arch::sync_scope;
-pub use silkenweb_task::{run_tasks, spawn_local, TaskSignal, TaskSignalVec};
+#[cfg(feature = "worker")]
+pub use silkenweb_task::worker;
+pub use silkenweb_task::{
+    run_tasks, spawn_abortable, spawn_local, Resource, ResourceState, TaskGroup, TaskHandle,
+    TaskSignal, TaskSignalVec,
+};
 
 #[cfg_browser(false)]
 /// Server only task tools.
 pub mod server {
-    pub use silkenweb_task::server::{block_on, run_tasks_sync};
+    use std::{error::Error, fmt, future::Future, sync::Arc, time::Duration};
 
-    use super::Render;
+    pub use silkenweb_task::server::{block_on, run_tasks_sync, LocalExecutorPool, TaskPanicked};
+
+    use super::{scope, Render};
 
     /// Synchronous version of [`render_now`][super::render_now].
     ///
@@ -40,6 +47,113 @@ pub mod server {
         Render::with(Render::render_effects);
         run_tasks_sync();
     }
+
+    /// A multi-threaded server side rendering service.
+    ///
+    /// Pages are rendered on a [`LocalExecutorPool`], so the futures returned
+    /// by [`Renderer::render`] are `Send` and can be used from multi-threaded
+    /// servers like `axum`. Each render is run in its own [`scope`], so task
+    /// locals, like the router's URL path and the document head, are isolated
+    /// between requests.
+    ///
+    /// Cloning a `Renderer` gives another handle to the same pool.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use html::{p, P};
+    /// # use silkenweb::{
+    /// #     prelude::*,
+    /// #     router,
+    /// #     task::{self, server::Renderer},
+    /// # };
+    /// # async fn handler(renderer: Renderer, path: String) {
+    /// let page_html = renderer
+    ///     .render(move || async move {
+    ///         router::set_url_path(path.as_str());
+    ///         let page: P = p().text(Sig(
+    ///             router::url_path().signal_ref(|url| url.as_str().to_string())
+    ///         ));
+    ///         task::render_now().await;
+    ///         page.freeze().to_string()
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    #[derive(Clone)]
+    pub struct Renderer {
+        pool: Arc<LocalExecutorPool>,
+        timeout: Option<Duration>,
+    }
+
+    impl Renderer {
+        /// Create a renderer with `thread_count` threads.
+        ///
+        /// # Panics
+        ///
+        /// If `thread_count` is 0.
+        pub fn new(thread_count: usize) -> Self {
+            Self {
+                pool: Arc::new(LocalExecutorPool::new(thread_count)),
+                timeout: None,
+            }
+        }
+
+        /// Fail any render that takes longer than `timeout`.
+        pub fn timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = Some(timeout);
+            self
+        }
+
+        /// Render a page.
+        ///
+        /// `f` is called on one of the pool's threads, in a new [`scope`]. The
+        /// future it returns should render the page and return the output,
+        /// usually the page's HTML.
+        pub fn render<F, Fut>(
+            &self,
+            f: F,
+        ) -> impl Future<Output = Result<Fut::Output, RenderError>> + Send + 'static
+        where
+            F: FnOnce() -> Fut + Send + 'static,
+            Fut: Future + 'static,
+            Fut::Output: Send + 'static,
+        {
+            let timeout = self.timeout;
+            let result = self.pool.spawn_pinned(move || {
+                scope(async move {
+                    match timeout {
+                        Some(timeout) => tokio::time::timeout(timeout, f())
+                            .await
+                            .map_err(|_| RenderError::Timeout),
+                        None => Ok(f().await),
+                    }
+                })
+            });
+
+            async move { result.await.map_err(|TaskPanicked| RenderError::Panicked)? }
+        }
+    }
+
+    /// A [`Renderer`] failed to render a page.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum RenderError {
+        /// The render took longer than the [`Renderer`]'s timeout.
+        Timeout,
+        /// The render panicked.
+        Panicked,
+    }
+
+    impl fmt::Display for RenderError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Timeout => f.write_str("Render timed out"),
+                Self::Panicked => f.write_str("Render panicked"),
+            }
+        }
+    }
+
+    impl Error for RenderError {}
 }
 
 #[cfg_browser(false)]
@@ -117,6 +231,13 @@ mod arch {
     }
 }
 
+/// Are we running inside a [`scope`] or [`sync_scope`]?
+///
+/// This is always `true` on browser platforms.
+pub fn in_scope() -> bool {
+    local::is_set()
+}
+
 /// Run a closure on the next animation frame.
 ///
 /// An animation frame will be requested with `requestAnimationFrame`.
@@ -20,6 +20,10 @@ mod arch {
     pub fn with<R>(f: impl FnOnce(&TaskLocal) -> R) -> R {
         TASK_LOCAL.with(f)
     }
+
+    pub fn is_set() -> bool {
+        true
+    }
 }
 
 #[cfg_browser(false)]
@@ -36,8 +40,12 @@ mod arch {
             Err(_) => panic!("Must be run from within `silkenweb::task::scope`"),
         }
     }
+
+    pub fn is_set() -> bool {
+        TASK_LOCAL.try_with(|_| ()).is_ok()
+    }
 }
 
-pub use arch::with;
 #[cfg_browser(false)]
 pub use arch::TASK_LOCAL;
+pub use arch::{is_set, with};
@@ -1,6 +1,19 @@
 //! Utilities for tacking time.
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::channel::oneshot;
+use futures_signals::signal::Signal;
+use pin_project::pin_project;
 use silkenweb_macros::cfg_browser;
 
+use crate::task::on_animation_frame;
+
 #[cfg_browser(true)]
 mod arch {
     use std::{future::Future, pin::Pin, task, time::Duration};
@@ -123,3 +136,290 @@ pub use arch::Interval;
 ///
 /// [`Future`]: std::future::Future
 pub use arch::Sleep;
+
+/// Time based [`Signal`] adaptors.
+///
+/// The initial value of the underlying signal is always yielded immediately.
+/// Only subsequent changes are affected by the timing of the adaptor.
+///
+/// # Example
+///
+/// Only search once the user has stopped typing for half a second:
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use futures_signals::signal::Mutable;
+/// # use silkenweb::{task::TaskSignal, time::TimeSignal};
+/// let search_text = Mutable::new(String::new());
+///
+/// search_text
+///     .signal_cloned()
+///     .debounce(Duration::from_millis(500))
+///     .spawn_for_each(|_text| async move {
+///         // Run the search
+///     });
+/// ```
+pub trait TimeSignal: Signal + Sized {
+    /// Only yield a value once the signal hasn't changed for `duration`.
+    ///
+    /// Each change restarts the timer, so a signal that keeps changing will
+    /// only yield its latest value once it settles.
+    fn debounce(self, duration: Duration) -> Debounce<Self> {
+        Debounce {
+            signal: Some(self),
+            sleep: None,
+            pending: None,
+            duration,
+            is_first: true,
+        }
+    }
+
+    /// Yield at most one value every `duration`.
+    ///
+    /// The first change is yielded immediately, and any changes within
+    /// `duration` of it are coalesced into one value yielded at the end of the
+    /// period. This is named `throttle_for` to avoid clashing with
+    /// [`SignalExt::throttle`].
+    ///
+    /// [`SignalExt::throttle`]: futures_signals::signal::SignalExt::throttle
+    fn throttle_for(self, duration: Duration) -> Throttle<Self> {
+        Throttle {
+            signal: Some(self),
+            sleep: None,
+            pending: None,
+            duration,
+        }
+    }
+
+    /// Delay each change by `duration`.
+    ///
+    /// Every change is yielded, in order, `duration` after it happened.
+    fn delay(self, duration: Duration) -> Delay<Self> {
+        Delay {
+            signal: Some(self),
+            queue: VecDeque::new(),
+            duration,
+            is_first: true,
+        }
+    }
+
+    /// Yield at most one value per animation frame.
+    ///
+    /// Changes are coalesced, and the latest value is yielded on the next
+    /// animation frame. This is useful for expensive updates driven by high
+    /// frequency events, like `resize` or `pointermove`.
+    fn sample_on_animation_frame(self) -> SampleOnAnimationFrame<Self> {
+        SampleOnAnimationFrame {
+            signal: Some(self),
+            frame: None,
+            pending: None,
+            is_first: true,
+        }
+    }
+}
+
+impl<Sig: Signal> TimeSignal for Sig {}
+
+/// [`Signal`] for [`TimeSignal::debounce`].
+#[must_use = "Signals do nothing unless polled"]
+#[pin_project]
+pub struct Debounce<Sig: Signal> {
+    #[pin]
+    signal: Option<Sig>,
+    #[pin]
+    sleep: Option<Sleep>,
+    pending: Option<Sig::Item>,
+    duration: Duration,
+    is_first: bool,
+}
+
+impl<Sig: Signal> Signal for Debounce<Sig> {
+    type Item = Sig::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut proj = self.project();
+
+        while let Some(Poll::Ready(change)) = poll_inner(proj.signal.as_mut(), cx) {
+            match change {
+                Some(value) if *proj.is_first => {
+                    *proj.is_first = false;
+                    return Poll::Ready(Some(value));
+                }
+                Some(value) => {
+                    *proj.pending = Some(value);
+                    proj.sleep.set(Some(sleep(*proj.duration)));
+                }
+                None => proj.signal.set(None),
+            }
+        }
+
+        if let Some(timer) = proj.sleep.as_mut().as_pin_mut() {
+            if timer.poll(cx).is_ready() {
+                proj.sleep.set(None);
+                return Poll::Ready(proj.pending.take());
+            }
+        }
+
+        if proj.signal.is_none() && proj.sleep.is_none() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// [`Signal`] for [`TimeSignal::throttle_for`].
+#[must_use = "Signals do nothing unless polled"]
+#[pin_project]
+pub struct Throttle<Sig: Signal> {
+    #[pin]
+    signal: Option<Sig>,
+    #[pin]
+    sleep: Option<Sleep>,
+    pending: Option<Sig::Item>,
+    duration: Duration,
+}
+
+impl<Sig: Signal> Signal for Throttle<Sig> {
+    type Item = Sig::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut proj = self.project();
+
+        while let Some(Poll::Ready(change)) = poll_inner(proj.signal.as_mut(), cx) {
+            match change {
+                Some(value) if proj.sleep.is_none() => {
+                    proj.sleep.set(Some(sleep(*proj.duration)));
+                    return Poll::Ready(Some(value));
+                }
+                Some(value) => *proj.pending = Some(value),
+                None => proj.signal.set(None),
+            }
+        }
+
+        let period_ended = proj
+            .sleep
+            .as_mut()
+            .as_pin_mut()
+            .is_some_and(|timer| timer.poll(cx).is_ready());
+
+        if period_ended {
+            if let Some(value) = proj.pending.take() {
+                proj.sleep.set(Some(sleep(*proj.duration)));
+                return Poll::Ready(Some(value));
+            }
+
+            proj.sleep.set(None);
+        }
+
+        if proj.signal.is_none() && proj.pending.is_none() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// [`Signal`] for [`TimeSignal::delay`].
+#[must_use = "Signals do nothing unless polled"]
+#[pin_project]
+pub struct Delay<Sig: Signal> {
+    #[pin]
+    signal: Option<Sig>,
+    queue: VecDeque<(Pin<Box<Sleep>>, Sig::Item)>,
+    duration: Duration,
+    is_first: bool,
+}
+
+impl<Sig: Signal> Signal for Delay<Sig> {
+    type Item = Sig::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut proj = self.project();
+
+        while let Some(Poll::Ready(change)) = poll_inner(proj.signal.as_mut(), cx) {
+            match change {
+                Some(value) if *proj.is_first => {
+                    *proj.is_first = false;
+                    return Poll::Ready(Some(value));
+                }
+                Some(value) => proj
+                    .queue
+                    .push_back((Box::pin(sleep(*proj.duration)), value)),
+                None => proj.signal.set(None),
+            }
+        }
+
+        if let Some((timer, _)) = proj.queue.front_mut() {
+            if timer.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(proj.queue.pop_front().map(|(_, value)| value));
+            }
+        }
+
+        if proj.signal.is_none() && proj.queue.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// [`Signal`] for [`TimeSignal::sample_on_animation_frame`].
+#[must_use = "Signals do nothing unless polled"]
+#[pin_project]
+pub struct SampleOnAnimationFrame<Sig: Signal> {
+    #[pin]
+    signal: Option<Sig>,
+    frame: Option<oneshot::Receiver<()>>,
+    pending: Option<Sig::Item>,
+    is_first: bool,
+}
+
+impl<Sig: Signal> Signal for SampleOnAnimationFrame<Sig> {
+    type Item = Sig::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut proj = self.project();
+
+        while let Some(Poll::Ready(change)) = poll_inner(proj.signal.as_mut(), cx) {
+            match change {
+                Some(value) if *proj.is_first => {
+                    *proj.is_first = false;
+                    return Poll::Ready(Some(value));
+                }
+                Some(value) => {
+                    *proj.pending = Some(value);
+
+                    if proj.frame.is_none() {
+                        let (frame_tx, frame_rx) = oneshot::channel();
+                        on_animation_frame(move || {
+                            let _ = frame_tx.send(());
+                        });
+                        *proj.frame = Some(frame_rx);
+                    }
+                }
+                None => proj.signal.set(None),
+            }
+        }
+
+        if let Some(frame) = proj.frame.as_mut() {
+            if Pin::new(frame).poll(cx).is_ready() {
+                *proj.frame = None;
+                return Poll::Ready(proj.pending.take());
+            }
+        }
+
+        if proj.signal.is_none() && proj.pending.is_none() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+fn poll_inner<Sig: Signal>(
+    signal: Pin<&mut Option<Sig>>,
+    cx: &mut Context,
+) -> Option<Poll<Option<Sig::Item>>> {
+    signal.as_pin_mut().map(|signal| signal.poll_change(cx))
+}
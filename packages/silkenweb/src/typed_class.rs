@@ -0,0 +1,90 @@
+//! Typed CSS class names.
+//!
+//! With the `typed` flag, [`css!`] generates a `class::Class` type for the
+//! stylesheet, with a constant (or function, with `auto_mount`) for each class.
+//! [`TypedClassElement::typed_class`] only accepts these types, so misspelt or
+//! removed class names are caught at compile time. Class names that aren't
+//! known at compile time can be converted with `Class::dynamic`.
+//!
+//! # Example
+//!
+//! ```
+//! # use html::{div, Div};
+//! # use silkenweb::{css, prelude::*, typed_class::TypedClassElement};
+//! css!(content = ".red { color: red } .bold { font-weight: bold }", typed);
+//!
+//! let app: Div = div()
+//!     .typed_class(class::Class::RED)
+//!     .typed_classes([class::Class::BOLD, class::Class::dynamic("other")]);
+//! ```
+//!
+//! [`css!`]: crate::css
+use crate::{
+    node::element::Element,
+    value::{RefSignalOrValue, Value},
+};
+
+/// A typed CSS class name, generated by [`css!`][crate::css].
+///
+/// [`name`][TypedClass::name] can contain several whitespace separated
+/// class names, for classes that use CSS Modules `composes`.
+pub trait TypedClass: Value + 'static {
+    /// The class name.
+    fn name(&self) -> &str;
+}
+
+/// Add typed classes to an element.
+pub trait TypedClassElement: Element {
+    /// Add a typed class to an element.
+    ///
+    /// This is like [`Element::class`], but it only accepts a [`TypedClass`].
+    fn typed_class<'a, C>(self, class: impl RefSignalOrValue<'a, Item = C>) -> Self
+    where
+        C: TypedClass,
+    {
+        self.classes(class.map(|class| ClassNames::new([class])))
+    }
+
+    /// Add typed classes to an element.
+    ///
+    /// This is like [`Element::classes`], but it only accepts [`TypedClass`]es.
+    fn typed_classes<'a, C, Iter>(self, classes: impl RefSignalOrValue<'a, Item = Iter>) -> Self
+    where
+        C: TypedClass,
+        Iter: 'a + IntoIterator<Item = C>,
+    {
+        self.classes(classes.map(ClassNames::new))
+    }
+}
+
+impl<E: Element> TypedClassElement for E {}
+
+struct ClassNames(Vec<String>);
+
+impl ClassNames {
+    fn new<C: TypedClass>(classes: impl IntoIterator<Item = C>) -> Self {
+        Self(
+            classes
+                .into_iter()
+                .flat_map(|class| {
+                    class
+                        .name()
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl IntoIterator for ClassNames {
+    type IntoIter = std::vec::IntoIter<String>;
+    type Item = String;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Value for ClassNames {}
@@ -35,3 +35,338 @@ isomorphic_test! {
         );
     }
 }
+
+mod custom_property {
+    use silkenweb::{
+        custom_property::{Angle, Color, Length, StyleCustomProperty, Time},
+        dom::DefaultDom,
+        elements::html::{div, Div},
+        node::element::Const,
+    };
+
+    silkenweb::css!(
+        content = r#"
+            @property --gap { syntax: '<length>'; inherits: false; initial-value: 0px; }
+            @property --accent { syntax: '<color>'; inherits: true; initial-value: black; }
+            @property --rotation { syntax: '<angle>'; inherits: false; initial-value: 0deg; }
+            @property --delay { syntax: '<time>'; inherits: false; initial-value: 0s; }
+            @property --image { syntax: '<url>'; inherits: false; initial-value: url(a.png); }
+        "#
+    );
+
+    isomorphic_test! {
+        async fn typed_custom_property() {
+            assert_eq!(property::GAP.name(), "--gap");
+            assert_eq!(property::GAP.initial_value(), Some("0px"));
+
+            let elem: Div<DefaultDom, Const> = div()
+                .custom_property(property::GAP, Length::px(4.0))
+                .freeze();
+            assert_eq!(elem.to_string(), r#"<div style="--gap: 4px;"></div>"#);
+        }
+    }
+
+    isomorphic_test! {
+        async fn color_property() {
+            assert_eq!(Color::rgb(1, 2, 3).to_string(), "rgb(1 2 3)");
+            assert_eq!(Color::rgba(1, 2, 3, 0.5).to_string(), "rgb(1 2 3 / 0.5)");
+            assert_eq!(Color::hsl(120.0, 50.0, 25.0).to_string(), "hsl(120deg 50% 25%)");
+            assert_eq!(Color::hex(0x0a0b0c).to_string(), "#0a0b0c");
+            assert_eq!(Color::named("red").to_string(), "red");
+
+            let elem: Div<DefaultDom, Const> = div()
+                .custom_property(property::ACCENT, Color::hex(0xff0000))
+                .freeze();
+            assert_eq!(elem.to_string(), r#"<div style="--accent: #ff0000;"></div>"#);
+        }
+    }
+
+    isomorphic_test! {
+        async fn angle_property() {
+            assert_eq!(Angle::deg(90.0).to_string(), "90deg");
+            assert_eq!(Angle::rad(1.5).to_string(), "1.5rad");
+            assert_eq!(Angle::turn(0.25).to_string(), "0.25turn");
+
+            let elem: Div<DefaultDom, Const> = div()
+                .custom_property(property::ROTATION, Angle::turn(0.5))
+                .freeze();
+            assert_eq!(elem.to_string(), r#"<div style="--rotation: 0.5turn;"></div>"#);
+        }
+    }
+
+    isomorphic_test! {
+        async fn time_property() {
+            assert_eq!(Time::s(2.0).to_string(), "2s");
+            assert_eq!(Time::ms(150.0).to_string(), "150ms");
+
+            let elem: Div<DefaultDom, Const> = div()
+                .custom_property(property::DELAY, Time::ms(150.0))
+                .freeze();
+            assert_eq!(elem.to_string(), r#"<div style="--delay: 150ms;"></div>"#);
+        }
+    }
+
+    isomorphic_test! {
+        async fn non_finite_values_are_clamped() {
+            assert_eq!(Length::px(f64::NAN).to_string(), "0px");
+            assert_eq!(Angle::deg(f64::INFINITY).to_string(), format!("{}deg", f64::MAX));
+            assert_eq!(Time::s(f64::NEG_INFINITY).to_string(), format!("{}s", f64::MIN));
+            assert_eq!(Color::rgba(0, 0, 0, f64::NAN).to_string(), "rgb(0 0 0 / 0)");
+        }
+    }
+
+    isomorphic_test! {
+        async fn unsupported_syntax_is_untyped() {
+            let elem: Div<DefaultDom, Const> = div()
+                .custom_property(property::IMAGE, "url(b.png)".to_string())
+                .freeze();
+            assert_eq!(elem.to_string(), r#"<div style="--image: url(b.png);"></div>"#);
+        }
+    }
+}
+
+mod scoped {
+    use silkenweb::{
+        dom::DefaultDom,
+        elements::html::{div, p, Div},
+        node::element::{Const, ParentElement},
+    };
+
+    silkenweb::css!(content = ".red, p::before { color: red }", scoped);
+
+    isomorphic_test! {
+        async fn scoped_stylesheet() {
+            let scope = stylesheet::SCOPE;
+            assert!(scope.starts_with("data-silkenweb-scope-"));
+            assert_eq!(
+                stylesheet::text(),
+                format!(".red[{scope}], p[{scope}]::before{{ color: red }}")
+            );
+
+            let elem: Div<DefaultDom, Const> =
+                stylesheet::scoped(|| div().child(p())).child(div()).freeze();
+            assert_eq!(
+                elem.to_string(),
+                format!(r#"<div {scope}=""><p {scope}=""></p><div></div></div>"#)
+            );
+        }
+    }
+
+    mod conditional {
+        silkenweb::css!(
+            content = "@media (min-width: 100px){@supports (display: grid){.grid{display:grid}}.wide{color:red}}",
+            scoped
+        );
+    }
+
+    isomorphic_test! {
+        async fn scoped_conditional_rules() {
+            let scope = conditional::stylesheet::SCOPE;
+            assert_eq!(
+                conditional::stylesheet::text(),
+                format!(
+                    "@media (min-width: 100px){{@supports (display: grid){{.grid[{scope}]{{display:grid}}}}.wide[{scope}]{{color:red}}}}"
+                )
+            );
+        }
+    }
+
+    mod legacy_pseudo_elements {
+        silkenweb::css!(
+            content = "p:before, a:hover:AFTER, li:first-child{color:red}",
+            scoped
+        );
+    }
+
+    isomorphic_test! {
+        async fn scoped_legacy_pseudo_elements() {
+            let scope = legacy_pseudo_elements::stylesheet::SCOPE;
+            assert_eq!(
+                legacy_pseudo_elements::stylesheet::text(),
+                format!("p[{scope}]:before, a:hover[{scope}]:AFTER, li:first-child[{scope}]{{color:red}}")
+            );
+        }
+    }
+}
+
+#[silkenweb_macros::cfg_browser(false)]
+mod critical_css {
+    use silkenweb::{
+        document::{self, Document},
+        dom::Dry,
+        task,
+    };
+
+    mod used {
+        silkenweb::css!(content = ".used { color: red }", auto_mount);
+    }
+
+    mod other {
+        silkenweb::css!(content = ".other { color: blue }", auto_mount);
+    }
+
+    mod bundled {
+        silkenweb::css!(content = ".bundled { color: green }", auto_mount, bundled);
+    }
+
+    fn render(use_all: bool) -> String {
+        assert_eq!(used::class::used(), "used");
+        used::stylesheet::mount_dom::<Dry>();
+
+        if use_all {
+            assert_eq!(other::class::other(), "other");
+        }
+
+        Dry::head_inner_html()
+    }
+
+    #[test]
+    fn inline_used_stylesheets() {
+        let style_html = format!(
+            r#"<style data-silkenweb-head-id="{}">.used {{ color: red }}</style>"#,
+            used::stylesheet::ID
+        );
+
+        // Each render has its own set of stylesheets.
+        for _ in 0..2 {
+            let head_html = task::server::block_on(task::scope(async { render(false) }));
+            assert_eq!(head_html, style_html);
+        }
+    }
+
+    #[test]
+    fn link_used_stylesheets() {
+        let head_html = task::server::block_on(task::scope(async {
+            document::link_stylesheets(|stylesheet| {
+                format!("/css/{}.css", stylesheet.text().len())
+            });
+            render(false)
+        }));
+
+        assert_eq!(
+            head_html,
+            format!(
+                r#"<link rel="stylesheet" href="/css/20.css" data-silkenweb-head-id="{}">"#,
+                used::stylesheet::ID
+            )
+        );
+        assert_eq!(
+            task::server::block_on(task::scope(async {
+                render(true);
+                document::used_stylesheets()
+                    .iter()
+                    .map(|stylesheet| stylesheet.id())
+                    .collect::<Vec<_>>()
+            })),
+            [used::stylesheet::ID, other::stylesheet::ID]
+        );
+    }
+
+    #[test]
+    fn bundled_stylesheets_are_not_mounted() {
+        let head_html = task::server::block_on(task::scope(async {
+            assert_eq!(bundled::class::bundled(), "bundled");
+            bundled::stylesheet::mount_dom::<Dry>();
+            Dry::head_inner_html()
+        }));
+
+        assert_eq!(head_html, "");
+        assert_eq!(bundled::stylesheet::text(), ".bundled { color: green }");
+    }
+}
+
+mod typed_class {
+    use silkenweb::{
+        dom::DefaultDom,
+        elements::html::{div, Div},
+        node::element::Const,
+        typed_class::TypedClassElement,
+    };
+
+    silkenweb::css!(
+        content = ".red { color: red } .bold-text { font-weight: bold }",
+        typed
+    );
+
+    isomorphic_test! {
+        async fn typed_classes() {
+            assert_eq!(class::RED, "red");
+
+            let elem: Div<DefaultDom, Const> = div()
+                .typed_class(class::Class::RED)
+                .typed_classes([class::Class::BOLD_TEXT, class::Class::dynamic("a b")])
+                .freeze();
+            assert_eq!(elem.to_string(), r#"<div class="red bold-text a b"></div>"#);
+        }
+    }
+}
+
+mod hydrate_stylesheet {
+    use silkenweb::{
+        document::{self, Document},
+        dom::Dry,
+        task::render_now,
+    };
+
+    silkenweb::css!(content = ".linked { color: green }");
+
+    isomorphic_test! {
+        async fn hydrate_linked_stylesheet() {
+            Dry::unmount_all();
+            document::link_stylesheets(|_| "/linked.css".to_string());
+            stylesheet::mount_dom::<Dry>();
+            let head_html = Dry::head_inner_html();
+            Dry::unmount_all();
+
+            assert_eq!(
+                head_html,
+                format!(
+                    r#"<link rel="stylesheet" href="/linked.css" data-silkenweb-head-id="{}">"#,
+                    stylesheet::ID
+                )
+            );
+
+            // Hydrating shouldn't replace the link, or add a `<style>`.
+            assert_eq!(hydrate_head(&head_html).await, head_html);
+        }
+    }
+
+    /// Hydrate the stylesheet against `head_html`, and return the head elements
+    /// for the stylesheet.
+    #[silkenweb_macros::cfg_browser(true)]
+    async fn hydrate_head(head_html: &str) -> String {
+        use silkenweb::dom::Hydro;
+
+        let head = silkenweb_base::document::head().unwrap();
+        head.insert_adjacent_html("beforeend", head_html).unwrap();
+        stylesheet::mount_dom::<Hydro>();
+        render_now().await;
+
+        let mut elements = Vec::new();
+        let mut child = head.first_element_child();
+
+        while let Some(elem) = child {
+            if elem.get_attribute("data-silkenweb-head-id").as_deref() == Some(stylesheet::ID) {
+                elements.push(elem.clone());
+            }
+
+            child = elem.next_element_sibling();
+        }
+
+        Hydro::unmount_all();
+        let html = elements.iter().map(|elem| elem.outer_html()).collect();
+
+        for elem in elements {
+            elem.remove();
+        }
+
+        html
+    }
+
+    /// There's no hydration on the server, so the head is left as it is.
+    #[silkenweb_macros::cfg_browser(false)]
+    async fn hydrate_head(head_html: &str) -> String {
+        render_now().await;
+        head_html.to_string()
+    }
+}
@@ -0,0 +1,156 @@
+use std::cell::Cell;
+
+use futures_signals::signal::Mutable;
+use silkenweb::{
+    custom_element::{define, CustomElement, Host},
+    custom_elements_manifest,
+    dom::{DefaultDom, Wet},
+    elements::{
+        html::{div, span},
+        HtmlElement,
+    },
+    mount,
+    node::{
+        element::{Const, GenericElement, ParentElement},
+        Node,
+    },
+    task::{render_now, run_tasks},
+    value::Sig,
+};
+use silkenweb_test::{html_element, BrowserTest};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::APP_ID;
+
+custom_elements_manifest!("tests/custom-elements.json");
+
+isomorphic_test! {
+    async fn manifest_element() {
+        let button = my_button::<DefaultDom>()
+            .label("Press me")
+            .disabled(true)
+            .icon_size(2.0)
+            .on_my_press(|_, _| {})
+            .icon_slot(span().text("*"))
+            .text("Press");
+
+        assert_eq!(
+            Node::from(button).to_string(),
+            r#"<my-button label="Press me" disabled="" icon-size="2"><span slot="icon">*</span>Press</my-button>"#
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn manifest_named_slots_only() {
+        let card = my_card::<DefaultDom>()
+            .set_elevation(2.0)
+            .header_slot(span().text("Header"));
+
+        assert_eq!(
+            Node::from(card).to_string(),
+            r#"<my-card><span slot="header">Header</span></my-card>"#
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+async fn manifest_property_setters() {
+    let _test = BrowserTest::new(APP_ID).await;
+    let value = Mutable::new("initial".to_string());
+    let button = my_button::<DefaultDom>()
+        .set_value(Sig(value.signal_cloned()))
+        .id("button");
+    let card = my_card::<DefaultDom>().set_elevation(2.0).id("card");
+
+    mount(APP_ID, div().child(button).child(card));
+    render_now().await;
+
+    let property = |id: &str, name: &str| {
+        js_sys::Reflect::get(&html_element(id), &JsValue::from_str(name)).unwrap()
+    };
+
+    assert_eq!(property("button", "value"), "initial");
+    assert_eq!(property("card", "elevation"), 2.0);
+
+    value.set("updated".to_string());
+    render_now().await;
+    assert_eq!(property("button", "value"), "updated");
+}
+
+thread_local! {
+    static LIVE_COUNTERS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The number of [`Counter`] values that haven't been dropped.
+fn live_counters() -> usize {
+    LIVE_COUNTERS.with(Cell::get)
+}
+
+struct Counter;
+
+impl CustomElement for Counter {
+    fn new(_host: &Host) -> Self {
+        LIVE_COUNTERS.with(|live| live.set(live.get() + 1));
+        Self
+    }
+
+    fn render(&self) -> GenericElement<Wet, Const> {
+        span().text("0").into()
+    }
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        LIVE_COUNTERS.with(|live| live.set(live.get() - 1));
+    }
+}
+
+#[wasm_bindgen_test]
+async fn instances_are_dropped() {
+    const NAME: &str = "silkenweb-test-counter";
+    const COUNT: usize = 3;
+
+    let _test = BrowserTest::new(APP_ID).await;
+    define::<Counter>(NAME).unwrap();
+    let document = web_sys::window().unwrap().document().unwrap();
+    let app = html_element(APP_ID);
+
+    // Elements that are never connected don't have an instance.
+    let _unconnected = document.create_element(NAME).unwrap();
+    assert_eq!(live_counters(), 0);
+
+    let elements: Vec<_> = (0..COUNT)
+        .map(|_| {
+            let element = document.create_element(NAME).unwrap();
+            app.append_child(&element).unwrap();
+            element
+        })
+        .collect();
+    assert_eq!(live_counters(), COUNT);
+
+    // Moving an element keeps its instance.
+    app.append_child(&elements[0]).unwrap();
+    run_tasks().await;
+    assert_eq!(live_counters(), COUNT);
+
+    for element in &elements {
+        element.remove();
+    }
+
+    run_tasks().await;
+    assert_eq!(live_counters(), 0);
+
+    // Reconnecting renders the element again.
+    app.append_child(&elements[0]).unwrap();
+    assert_eq!(live_counters(), 1);
+    assert_eq!(
+        elements[0].shadow_root().unwrap().inner_html(),
+        "<span>0</span>"
+    );
+
+    elements[0].remove();
+    run_tasks().await;
+    assert_eq!(live_counters(), 0);
+}
@@ -12,11 +12,25 @@ use silkenweb::{
         element::{Element, ParentElement},
         text, Node,
     },
-    task::render_now,
+    task::{render_now, run_tasks, TaskGroup},
     value::Sig,
 };
 use silkenweb_macros::cfg_browser;
 
+isomorphic_test! {
+    async fn task_group_lifetime() {
+        let tasks = TaskGroup::new();
+        let elem: Div = div().task_group(tasks.clone());
+        let handle = tasks.spawn(futures::future::pending::<()>());
+        drop(tasks);
+        run_tasks().await;
+        assert!(!handle.is_aborted(), "The element keeps the group alive");
+
+        drop(elem);
+        assert!(handle.is_aborted(), "Tasks are aborted with the element");
+    }
+}
+
 macro_rules! render_test {
     ($name:ident, $node:expr, $expected:expr) => {
         isomorphic_test! {
@@ -337,3 +351,38 @@ pub async fn children_signal_test(
     with_existing_children(div(), "", initial, f.clone(), expected).await;
     with_existing_children(div().child(div()), "<div></div>", initial, f, expected).await;
 }
+
+#[test]
+fn element_metadata() {
+    use silkenweb::elements::metadata::{self, AttributeType, HTML_ELEMENTS, SVG_ELEMENTS};
+
+    let find = |elements: &'static [metadata::Element], name| {
+        elements
+            .iter()
+            .find(|element| element.name == name)
+            .unwrap()
+    };
+
+    let br = find(HTML_ELEMENTS, "br");
+    assert!(!br.is_parent);
+
+    let ol = find(HTML_ELEMENTS, "ol");
+    assert!(ol.is_parent);
+    assert_eq!(ol.attribute("start").unwrap().typ, AttributeType::U32);
+    assert_eq!(ol.attribute("reversed").unwrap().typ, AttributeType::Bool);
+    assert_eq!(ol.attribute("type").unwrap().method, "r#type");
+
+    let video = find(HTML_ELEMENTS, "video");
+    assert_eq!(
+        video.attribute("currentTime").unwrap().method,
+        "current_time"
+    );
+
+    let clip_path = find(SVG_ELEMENTS, "clipPath");
+    assert_eq!(clip_path.fn_name, "clip_path");
+    assert!(clip_path.is_parent);
+
+    let svg_use = find(SVG_ELEMENTS, "use");
+    assert_eq!(svg_use.fn_name, "r#use");
+    assert!(!svg_use.is_parent);
+}
@@ -0,0 +1,229 @@
+use silkenweb::{
+    elements::html::{div, input, option, select, textarea, Input},
+    form::{BindChecked, BindMultiple, BindValue, Field, Form, Validity},
+    mount,
+    prelude::{HtmlElement, ParentElement},
+    task::{render_now, run_tasks},
+};
+use silkenweb_test::{html_element, BrowserTest};
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlInputElement, HtmlOptionElement, HtmlSelectElement, HtmlTextAreaElement};
+
+use crate::APP_ID;
+
+fn required(value: &String) -> Result<(), String> {
+    if value.is_empty() {
+        Err("Required".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+isomorphic_test! {
+    async fn field_validation() {
+        let name = Field::new(String::new()).validator(|name: &String| {
+            if name.is_empty() {
+                Err("Required".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        let form = Form::new().field(&name);
+
+        run_tasks().await;
+        assert_eq!(name.validity(), Validity::Invalid("Required".to_string()));
+        assert!(form.submit(|| name.get()).is_none());
+
+        name.set("Alice".to_string());
+        run_tasks().await;
+        assert_eq!(name.validity(), Validity::Valid);
+        assert_eq!(form.submit(|| name.get()), Some("Alice".to_string()));
+    }
+}
+
+isomorphic_test! {
+    async fn async_validation() {
+        let age = Field::new(0u32).async_validator(|age| async move {
+            if age >= 18 {
+                Ok(())
+            } else {
+                Err("Too young".to_string())
+            }
+        });
+
+        run_tasks().await;
+        assert_eq!(age.validity(), Validity::Invalid("Too young".to_string()));
+
+        age.set(21);
+        run_tasks().await;
+        assert_eq!(age.validity(), Validity::Valid);
+    }
+}
+
+isomorphic_test! {
+    async fn eager_validation() {
+        let name = Field::new(String::new()).validator(required);
+        let form = Form::new().field(&name);
+
+        assert_eq!(
+            name.validity(),
+            Validity::Invalid("Required".to_string()),
+            "Validated on creation"
+        );
+        assert!(form.submit(|| name.get()).is_none());
+
+        name.set("Alice".to_string());
+        assert_eq!(form.submit(|| name.get()), Some("Alice".to_string()));
+
+        name.set(String::new());
+        assert!(form.submit(|| name.get()).is_none(), "Validated on `set`");
+
+        name.mutable().set("Bob".to_string());
+        run_tasks().await;
+        assert_eq!(name.validity(), Validity::Valid, "Validated on `Mutable` changes");
+    }
+}
+
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn bind_value() {
+    let _test = BrowserTest::new(APP_ID).await;
+
+    let age = Field::new(18u32);
+    mount(APP_ID, input().id("age").bind_value(&age));
+    render_now().await;
+
+    let elem: HtmlInputElement = html_element("age").unchecked_into();
+    assert_eq!(elem.value(), "18", "Field to control");
+
+    age.set(21);
+    render_now().await;
+    assert_eq!(elem.value(), "21", "Field updates control");
+
+    elem.set_value("30");
+    dispatch(&elem, "input");
+    assert_eq!(age.get(), 30, "Control updates field");
+    assert_eq!(age.validity(), Validity::Valid);
+
+    elem.set_value("thirty");
+    dispatch(&elem, "input");
+    assert_eq!(age.get(), 30, "Invalid input leaves the value unchanged");
+    assert_eq!(
+        age.validity(),
+        Validity::Invalid("\"thirty\" isn't a valid number".to_string())
+    );
+}
+
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn bind_textarea() {
+    let _test = BrowserTest::new(APP_ID).await;
+
+    let comment = Field::new(String::new()).validator(required);
+    mount(APP_ID, textarea().id("comment").bind_value(&comment));
+    render_now().await;
+
+    let elem: HtmlTextAreaElement = html_element("comment").unchecked_into();
+    elem.set_value("Hello");
+    dispatch(&elem, "input");
+    assert_eq!(comment.get(), "Hello");
+    assert_eq!(comment.validity(), Validity::Valid);
+
+    comment.set("Goodbye".to_string());
+    render_now().await;
+    assert_eq!(elem.value(), "Goodbye");
+}
+
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn bind_checked() {
+    let _test = BrowserTest::new(APP_ID).await;
+
+    let agree = Field::new(false);
+    mount(
+        APP_ID,
+        input().id("agree").r#type("checkbox").bind_checked(&agree),
+    );
+    render_now().await;
+
+    let elem: HtmlInputElement = html_element("agree").unchecked_into();
+    assert!(!elem.checked());
+
+    agree.set(true);
+    render_now().await;
+    assert!(elem.checked(), "Field updates control");
+
+    elem.set_checked(false);
+    dispatch(&elem, "change");
+    assert!(!agree.get(), "Control updates field");
+}
+
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn bind_radio() {
+    let _test = BrowserTest::new(APP_ID).await;
+
+    let size = Field::new("small".to_string());
+    let radio = |value: &str| -> Input {
+        input()
+            .id(value)
+            .r#type("radio")
+            .name("size")
+            .bind_radio(&size, value.to_string())
+    };
+    mount(APP_ID, div().child(radio("small")).child(radio("large")));
+    render_now().await;
+
+    let small: HtmlInputElement = html_element("small").unchecked_into();
+    let large: HtmlInputElement = html_element("large").unchecked_into();
+    assert!(small.checked());
+    assert!(!large.checked());
+
+    large.set_checked(true);
+    dispatch(&large, "change");
+    assert_eq!(size.get(), "large", "Control updates field");
+
+    size.set("small".to_string());
+    render_now().await;
+    assert!(small.checked(), "Field updates control");
+}
+
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn bind_multiple() {
+    let _test = BrowserTest::new(APP_ID).await;
+
+    let sizes = Field::new(vec![1u32]);
+    mount(
+        APP_ID,
+        select()
+            .id("sizes")
+            .children(
+                (1..=3).map(|value| option().value(value.to_string()).text(value.to_string())),
+            )
+            .bind_multiple(&sizes),
+    );
+    render_now().await;
+
+    let elem: HtmlSelectElement = html_element("sizes").unchecked_into();
+    assert_eq!(selected(&elem), ["1"], "Field to control");
+
+    sizes.set(vec![2, 3]);
+    render_now().await;
+    assert_eq!(selected(&elem), ["2", "3"], "Field updates control");
+
+    option_element(&elem, 0).set_selected(true);
+    dispatch(&elem, "change");
+    assert_eq!(sizes.get(), [1, 2, 3], "Control updates field");
+}
+
+fn dispatch(target: &web_sys::EventTarget, name: &str) {
+    target.dispatch_event(&Event::new(name).unwrap()).unwrap();
+}
+
+fn option_element(select: &HtmlSelectElement, index: u32) -> HtmlOptionElement {
+    select.item(index).unwrap().unchecked_into()
+}
+
+fn selected(select: &HtmlSelectElement) -> Vec<String> {
+    (0..select.length())
+        .map(|index| option_element(select, index))
+        .filter(HtmlOptionElement::selected)
+        .map(|option| option.value())
+        .collect()
+}
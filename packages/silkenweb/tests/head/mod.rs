@@ -77,6 +77,18 @@ fn dry_basic() {
     task::server::block_on(task::scope(basic::<Dry>()))
 }
 
+#[cfg_browser(false)]
+#[test]
+fn dry_used_head_ids() {
+    use silkenweb::{document::used_head_ids, dom::Dry, task};
+
+    task::server::block_on(task::scope(async {
+        Dry::mount_stylesheet("sheet", ".a {}");
+        Dry::mount_in_head("my-id", DocumentHead::new().child(meta()));
+        assert_eq!(used_head_ids(), ["sheet", "my-id"]);
+    }))
+}
+
 #[wasm_bindgen_test]
 async fn wet_basic() {
     basic::<Wet>().await
@@ -0,0 +1,123 @@
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::{
+    dom::DefaultDom,
+    elements::{
+        html::{p, Div, Ul, P},
+        svg::Svg,
+    },
+    html, mount,
+    task::render_now,
+    value::Sig,
+};
+use silkenweb_test::{html_element, BrowserTest};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::APP_ID;
+
+isomorphic_test! {
+    async fn html_macro() {
+        let text = Mutable::new("Hello");
+        let items = ["one", "two"];
+        let elem: Div<DefaultDom> = html! {
+            <div class="a b" id="my-id" data-test="x">
+                "Text "
+                {Sig(text.signal())}
+                <ul>
+                    {..items.iter().map(|item| html!(<li>{*item}</li>))}
+                </ul>
+                <input type="text" />
+            </div>
+        };
+        let elem = elem.freeze();
+        let expected = |text: &str| {
+            format!(
+                r#"<div class="a b" id="my-id" data-test="x">Text {text}<ul><li>one</li><li>two</li></ul><input type="text"></div>"#
+            )
+        };
+
+        render_now().await;
+        assert_eq!(elem.to_string(), expected("Hello"));
+
+        text.set("World");
+        render_now().await;
+        assert_eq!(elem.to_string(), expected("World"));
+    }
+}
+
+isomorphic_test! {
+    async fn iter_children() {
+        let empty: Ul<DefaultDom> = html!(<ul>{..Vec::<P<DefaultDom>>::new()}</ul>);
+        assert_eq!(empty.freeze().to_string(), "<ul></ul>");
+
+        let elem: Ul<DefaultDom> = html! {
+            <ul>
+                <li>"first"</li>
+                {..(1..=2).map(|i| html!(<li>{i.to_string()}</li>))}
+                <li>"last"</li>
+            </ul>
+        };
+        assert_eq!(
+            elem.freeze().to_string(),
+            "<ul><li>first</li><li>1</li><li>2</li><li>last</li></ul>"
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn node_signal_child() {
+        let text = Mutable::new("Hello");
+        let elem: Div<DefaultDom> = html! {
+            <div>{Sig(text.signal().map(|text| p::<DefaultDom>().text(text)))}</div>
+        };
+        let elem = elem.freeze();
+
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><p>Hello</p></div>");
+
+        text.set("World");
+        render_now().await;
+        assert_eq!(elem.to_string(), "<div><p>World</p></div>");
+    }
+}
+
+isomorphic_test! {
+    async fn svg_children() {
+        let elem: Svg<DefaultDom> = html! {
+            <svg width={200.0}>
+                <rect class="shape" />
+            </svg>
+        };
+        assert_eq!(
+            elem.freeze().to_string(),
+            r#"<svg width="200"><rect class="shape"></rect></svg>"#
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+async fn on_event() {
+    const BUTTON_ID: &str = "html-macro-button";
+    const COUNTER_ID: &str = "html-macro-counter";
+
+    let _test = BrowserTest::new(APP_ID).await;
+
+    let count = Mutable::new(0);
+    let count_text = Sig(count.signal_ref(|i| i.to_string()));
+
+    let app: Div = html! {
+        <div id={COUNTER_ID}>
+            <button id={BUTTON_ID} on:click={move |_, _| { count.replace_with(|i| *i + 1); }}>
+                "+"
+            </button>
+            {count_text}
+        </div>
+    };
+    mount(APP_ID, app);
+
+    render_now().await;
+    let counter_text = || html_element(COUNTER_ID).inner_text();
+    assert_eq!("+0", counter_text());
+    html_element(BUTTON_ID).click();
+    render_now().await;
+    assert_eq!("+1", counter_text());
+}
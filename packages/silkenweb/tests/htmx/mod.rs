@@ -0,0 +1,109 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::{
+    dom::Dry,
+    elements::html::{div, Div},
+    htmx::{HtmxElement, Json, Queue, ScrollPosition, Swap, SwapStyle, Trigger},
+    task::render_now,
+    value::Sig,
+};
+
+#[test]
+fn swap_styles() {
+    let styles = [
+        (SwapStyle::InnerHtml, "innerHTML"),
+        (SwapStyle::OuterHtml, "outerHTML"),
+        (SwapStyle::BeforeBegin, "beforebegin"),
+        (SwapStyle::AfterBegin, "afterbegin"),
+        (SwapStyle::BeforeEnd, "beforeend"),
+        (SwapStyle::AfterEnd, "afterend"),
+        (SwapStyle::Delete, "delete"),
+        (SwapStyle::None, "none"),
+    ];
+
+    for (style, text) in styles {
+        assert_eq!(style.to_string(), text);
+        assert_eq!(Swap::from(style).to_string(), text);
+    }
+}
+
+#[test]
+fn swap_modifiers() {
+    let swap = Swap::new(SwapStyle::OuterHtml)
+        .swap(Duration::from_millis(100))
+        .settle(Duration::from_secs(1))
+        .transition(true)
+        .ignore_title(false)
+        .scroll(ScrollPosition::Top, None)
+        .show(ScrollPosition::Bottom, Some("#list"))
+        .focus_scroll(true);
+
+    assert_eq!(
+        swap.to_string(),
+        "outerHTML swap:100ms settle:1000ms transition:true ignoreTitle:false scroll:top \
+         show:#list:bottom focus-scroll:true"
+    );
+}
+
+#[test]
+fn trigger_modifiers() {
+    let trigger = Trigger::event("click")
+        .filter("ctrlKey")
+        .once()
+        .consume()
+        .from("body")
+        .target("button")
+        .queue(Queue::Last)
+        .throttle(Duration::from_millis(250));
+
+    assert_eq!(
+        trigger.to_string(),
+        "click[ctrlKey] once consume from:body target:button queue:last throttle:250ms"
+    );
+}
+
+#[test]
+fn trigger_events() {
+    let trigger = Trigger::every(Duration::from_secs(2))
+        .or(Trigger::event("load").once())
+        .or(Trigger::event("keyup").filter("key=='Enter'"));
+
+    assert_eq!(
+        trigger.to_string(),
+        "every 2000ms, load once, keyup[key=='Enter']"
+    );
+}
+
+#[test]
+fn json() {
+    let vals = Json::new(BTreeMap::from([("a", 1), ("b", 2)])).unwrap();
+    assert_eq!(vals.to_string(), r#"{"a":1,"b":2}"#);
+
+    // JSON object keys must be strings.
+    assert!(Json::new(BTreeMap::from([((1, 2), 3)])).is_err());
+}
+
+fn vals_element(vals: &Mutable<Option<u32>>) -> Div<Dry> {
+    div().hx_vals(Sig(vals.signal().map(|id| {
+        id.and_then(|id| Json::new(BTreeMap::from([("id", id)])).ok())
+    })))
+}
+
+isomorphic_test! {
+    async fn json_signal() {
+        let vals = Mutable::new(Some(1));
+        let elem = vals_element(&vals);
+        vals.set(Some(2));
+        render_now().await;
+        assert_eq!(
+            elem.freeze().to_string(),
+            r#"<div hx-vals="{&quot;id&quot;:2}"></div>"#
+        );
+
+        let elem = vals_element(&vals);
+        vals.set(None);
+        render_now().await;
+        assert_eq!(elem.freeze().to_string(), "<div></div>");
+    }
+}
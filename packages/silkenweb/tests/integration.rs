@@ -31,10 +31,16 @@ macro_rules! isomorphic_test {
 mod children;
 mod component;
 mod css;
+mod custom_elements;
 mod element;
+mod form;
 mod head;
+mod html_macro;
+#[cfg(feature = "htmx")]
+mod htmx;
 mod hydration;
 mod template;
+mod time;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -0,0 +1,5 @@
+use silkenweb::{elements::html::Div, html};
+
+fn main() {
+    let _: Div = html!(<div foo="x"></div>);
+}
@@ -0,0 +1,5 @@
+use silkenweb::{elements::html::Div, html};
+
+fn main() {
+    let _: Div = html!(<foo></foo>);
+}
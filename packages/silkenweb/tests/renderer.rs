@@ -0,0 +1,91 @@
+#![cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+use futures::{channel::oneshot, future};
+use silkenweb::{
+    document::{self, Document},
+    dom::Dry,
+    router,
+    task::server::{block_on, RenderError, Renderer},
+};
+
+#[test]
+fn render() {
+    let renderer = Renderer::new(1);
+    assert_eq!(block_on(renderer.render(|| async { 1 })), Ok(1));
+}
+
+#[test]
+fn timeout() {
+    let renderer = Renderer::new(1).timeout(Duration::from_millis(10));
+
+    assert_eq!(
+        block_on(renderer.render(|| future::pending::<()>())),
+        Err(RenderError::Timeout)
+    );
+    assert_eq!(
+        block_on(renderer.render(|| async { 1 })),
+        Ok(1),
+        "Renders within the timeout succeed"
+    );
+}
+
+#[test]
+fn panic() {
+    let renderer = Renderer::new(1);
+
+    assert_eq!(
+        block_on(renderer.render(|| async { panic!("Render failed") })),
+        Err::<(), _>(RenderError::Panicked)
+    );
+    assert_eq!(
+        block_on(renderer.render(|| async { 1 })),
+        Ok(1),
+        "The renderer survives a panic"
+    );
+}
+
+/// Run 2 renders concurrently on the same thread, and check they don't see
+/// each other's task locals.
+#[test]
+fn isolation() {
+    let renderer = Renderer::new(1);
+    let (first_ready, wait_for_first) = oneshot::channel();
+    let (second_done, wait_for_second) = oneshot::channel();
+
+    let first = renderer.render(|| async move {
+        router::set_url_path("/first");
+        Dry::mount_stylesheet("first", ".first {}");
+        first_ready.send(()).unwrap();
+        wait_for_second.await.unwrap();
+
+        (
+            router::url_path().get_cloned().as_str().to_string(),
+            stylesheet_ids(),
+        )
+    });
+
+    let second = renderer.render(|| async move {
+        wait_for_first.await.unwrap();
+        router::set_url_path("/second");
+        Dry::mount_stylesheet("second", ".second {}");
+        let result = (
+            router::url_path().get_cloned().as_str().to_string(),
+            stylesheet_ids(),
+        );
+        second_done.send(()).unwrap();
+        result
+    });
+
+    let (first, second) = block_on(future::join(first, second));
+
+    assert_eq!(first, Ok(("/first".to_string(), vec!["first"])));
+    assert_eq!(second, Ok(("/second".to_string(), vec!["second"])));
+}
+
+fn stylesheet_ids() -> Vec<&'static str> {
+    document::used_stylesheets()
+        .iter()
+        .map(|stylesheet| stylesheet.id())
+        .collect()
+}
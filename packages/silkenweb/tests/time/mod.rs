@@ -0,0 +1,181 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use futures_signals::signal::{Mutable, Signal};
+use silkenweb::{
+    task::{render_now, run_tasks, TaskSignal},
+    time::{sleep, TimeSignal},
+};
+
+/// Like `isomorphic_test!`, but with a `tokio` runtime for timers on the
+/// server.
+///
+/// The server runtime's clock is paused, so it skips ahead to the next timer
+/// whenever it's idle. This makes the timings exact, however loaded the
+/// machine is.
+macro_rules! timer_test {
+    (async fn $name:ident() $body:block) => {
+        #[silkenweb_macros::cfg_browser(false)]
+        #[test]
+        fn $name() {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .start_paused(true)
+                .build()
+                .unwrap()
+                .block_on(::silkenweb::task::scope(async { $body }));
+        }
+
+        #[silkenweb_macros::cfg_browser(true)]
+        #[wasm_bindgen_test::wasm_bindgen_test]
+        async fn $name() {
+            $body
+        }
+    };
+}
+
+isomorphic_test! {
+    async fn sample_on_animation_frame() {
+        let source = Mutable::new(0);
+        let sampled = source.signal().sample_on_animation_frame().to_mutable();
+        assert_eq!(sampled.get(), 0, "Initial value is immediate");
+
+        source.set(1);
+        source.set(2);
+        run_tasks().await;
+        assert_eq!(sampled.get(), 0, "Changes wait for an animation frame");
+
+        render_now().await;
+        run_tasks().await;
+        assert_eq!(sampled.get(), 2, "Latest value after an animation frame");
+    }
+}
+
+timer_test! {
+    async fn debounce() {
+        let source = Mutable::new(0);
+        let values = collect(source.signal().debounce(PERIOD)).await;
+        assert_eq!(*values.borrow(), [0], "Initial value is immediate");
+
+        source.set(1);
+        wait(PERIOD / 4).await;
+        source.set(2);
+        wait(PERIOD / 4).await;
+        source.set(3);
+        wait(PERIOD / 2).await;
+        assert_eq!(*values.borrow(), [0], "Each change restarts the timer");
+
+        wait(PERIOD).await;
+        assert_eq!(*values.borrow(), [0, 3], "Only the trailing value of a burst");
+    }
+}
+
+timer_test! {
+    async fn debounce_after_drop() {
+        let source = Mutable::new(0);
+        let values = collect(source.signal().debounce(PERIOD)).await;
+
+        source.set(1);
+        drop(source);
+        wait(PERIOD * 2).await;
+        assert_eq!(
+            *values.borrow(),
+            [0, 1],
+            "Pending values are yielded after the source ends"
+        );
+    }
+}
+
+timer_test! {
+    async fn throttle_for() {
+        let source = Mutable::new(0);
+        let values = collect(source.signal().throttle_for(PERIOD)).await;
+        assert_eq!(*values.borrow(), [0], "Initial value is immediate");
+
+        source.set(1);
+        source.set(2);
+        wait(PERIOD / 2).await;
+        assert_eq!(*values.borrow(), [0], "Changes within the period are held");
+
+        wait(PERIOD).await;
+        assert_eq!(*values.borrow(), [0, 2], "Trailing value at the end of the period");
+
+        wait(PERIOD * 2).await;
+        source.set(3);
+        run_tasks().await;
+        assert_eq!(
+            *values.borrow(),
+            [0, 2, 3],
+            "Changes after a quiet period are immediate"
+        );
+    }
+}
+
+timer_test! {
+    async fn throttle_for_after_drop() {
+        let source = Mutable::new(0);
+        let values = collect(source.signal().throttle_for(PERIOD)).await;
+
+        source.set(1);
+        drop(source);
+        wait(PERIOD * 2).await;
+        assert_eq!(
+            *values.borrow(),
+            [0, 1],
+            "Pending values are yielded after the source ends"
+        );
+    }
+}
+
+timer_test! {
+    async fn delay() {
+        let source = Mutable::new(0);
+        let values = collect(source.signal().delay(PERIOD)).await;
+        assert_eq!(*values.borrow(), [0], "Initial value is immediate");
+
+        source.set(1);
+        wait(PERIOD / 2).await;
+        source.set(2);
+        wait(PERIOD / 4).await;
+        assert_eq!(*values.borrow(), [0], "Changes are delayed");
+
+        wait(PERIOD / 2).await;
+        assert_eq!(*values.borrow(), [0, 1], "The first change is yielded first");
+
+        drop(source);
+        wait(PERIOD).await;
+        assert_eq!(
+            *values.borrow(),
+            [0, 1, 2],
+            "Every change is yielded in order, even after the source ends"
+        );
+    }
+}
+
+/// The server clock is paused, so the period doesn't affect how long the tests
+/// take.
+#[silkenweb_macros::cfg_browser(false)]
+const PERIOD: Duration = Duration::from_millis(200);
+
+/// Browsers use the real clock, so leave plenty of time between steps.
+#[silkenweb_macros::cfg_browser(true)]
+const PERIOD: Duration = Duration::from_secs(1);
+
+async fn collect<T: 'static>(signal: impl Signal<Item = T> + 'static) -> Rc<RefCell<Vec<T>>> {
+    let values = Rc::new(RefCell::new(Vec::new()));
+
+    signal.spawn_for_each({
+        let values = values.clone();
+        move |value| {
+            values.borrow_mut().push(value);
+            async {}
+        }
+    });
+    run_tasks().await;
+
+    values
+}
+
+async fn wait(duration: Duration) {
+    sleep(duration).await;
+    run_tasks().await;
+}
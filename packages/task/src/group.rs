@@ -0,0 +1,237 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use futures::{
+    channel::oneshot,
+    future::{abortable, poll_fn, AbortHandle, Aborted},
+    Future,
+};
+
+use crate::spawn_local;
+
+/// A handle to a task spawned with [`spawn_abortable`] or
+/// [`TaskGroup::spawn`].
+///
+/// The handle is a future that resolves to the task's output, or [`Aborted`]
+/// if the task was aborted. Dropping the handle detaches the task, so it will
+/// continue to run.
+#[must_use = "Dropping a `TaskHandle` detaches the task"]
+pub struct TaskHandle<T> {
+    abort_handle: AbortHandle,
+    result: oneshot::Receiver<T>,
+}
+
+impl<T> TaskHandle<T> {
+    /// Abort the task.
+    ///
+    /// The task's future will be dropped without being polled again.
+    pub fn abort(&self) {
+        self.abort_handle.abort();
+    }
+
+    /// Has [`Self::abort`] been called on this task, or its group?
+    pub fn is_aborted(&self) -> bool {
+        self.abort_handle.is_aborted()
+    }
+}
+
+impl<T> Future for TaskHandle<T> {
+    type Output = Result<T, Aborted>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.result)
+            .poll(cx)
+            .map(|result| result.map_err(|_| Aborted))
+    }
+}
+
+/// Spawn a future on the microtask queue, returning a handle to it.
+///
+/// Use the handle to abort the task or `await` its result.
+pub fn spawn_abortable<F>(future: F) -> TaskHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    spawn_task(future, || ())
+}
+
+/// A group of tasks that are aborted together.
+///
+/// Cloning a `TaskGroup` gives another handle to the same group. All tasks in
+/// the group are aborted when every handle has been dropped, so you can store
+/// a `TaskGroup` in a component to tie the lifetime of its tasks to the
+/// component.
+///
+/// # Example
+///
+/// ```rust
+/// # use futures_signals::signal::Mutable;
+/// # use silkenweb_task::{server::run_tasks_sync, sync_scope, TaskGroup};
+/// #
+/// let count = Mutable::new(0);
+///
+/// // A scope isn't required on browser platforms
+/// sync_scope(|| {
+///     let group = TaskGroup::new();
+///     let never_finishes = group.spawn(futures::future::pending::<()>());
+///     let _increment = group.spawn({
+///         let count = count.clone();
+///         async move { count.replace_with(|count| *count + 1) }
+///     });
+///
+///     run_tasks_sync();
+///     assert_eq!(count.get(), 1);
+///     assert!(!never_finishes.is_aborted());
+///
+///     drop(group);
+///     assert!(never_finishes.is_aborted());
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct TaskGroup(Rc<GroupTasks>);
+
+impl TaskGroup {
+    /// Create an empty task group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` with a new group, then wait for all tasks in the group to
+    /// finish.
+    ///
+    /// This provides structured concurrency for child tasks. If the returned
+    /// future is dropped before it completes, all tasks in the group are
+    /// aborted, unless a clone of the group has been kept elsewhere.
+    pub async fn scope<F, Fut>(f: F) -> Fut::Output
+    where
+        F: FnOnce(TaskGroup) -> Fut,
+        Fut: Future,
+    {
+        let group = Self::new();
+        let output = f(group.clone()).await;
+        group.join().await;
+        output
+    }
+
+    /// Spawn a future on the microtask queue, as part of this group.
+    pub fn spawn<F>(&self, future: F) -> TaskHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id.wrapping_add(1));
+        let tasks = Rc::downgrade(&self.0);
+
+        let handle = spawn_task(future, move || {
+            if let Some(tasks) = tasks.upgrade() {
+                tasks.remove(id);
+            }
+        });
+
+        self.0
+            .running
+            .borrow_mut()
+            .insert(id, handle.abort_handle.clone());
+
+        handle
+    }
+
+    /// Abort all tasks that are currently running in this group.
+    ///
+    /// The group can still be used to spawn new tasks.
+    pub fn abort_all(&self) {
+        self.0.abort_all();
+        self.0.wake_idle();
+    }
+
+    /// Are there no tasks running in this group?
+    pub fn is_idle(&self) -> bool {
+        self.0.running.borrow().is_empty()
+    }
+
+    /// Wait until there are no tasks running in this group.
+    pub async fn join(&self) {
+        poll_fn(|cx| {
+            if self.is_idle() {
+                Poll::Ready(())
+            } else {
+                let mut idle_wakers = self.0.idle_wakers.borrow_mut();
+
+                if !idle_wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    idle_wakers.push(cx.waker().clone());
+                }
+
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+#[derive(Default)]
+struct GroupTasks {
+    next_id: Cell<u64>,
+    running: RefCell<HashMap<u64, AbortHandle>>,
+    idle_wakers: RefCell<Vec<Waker>>,
+}
+
+impl GroupTasks {
+    fn remove(&self, id: u64) {
+        let is_idle = {
+            let mut running = self.running.borrow_mut();
+            running.remove(&id);
+            running.is_empty()
+        };
+
+        if is_idle {
+            self.wake_idle();
+        }
+    }
+
+    fn abort_all(&self) {
+        for (_id, abort_handle) in self.running.take() {
+            abort_handle.abort();
+        }
+    }
+
+    fn wake_idle(&self) {
+        for waker in self.idle_wakers.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for GroupTasks {
+    fn drop(&mut self) {
+        self.abort_all();
+    }
+}
+
+fn spawn_task<F>(future: F, on_complete: impl FnOnce() + 'static) -> TaskHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    let (result_tx, result_rx) = oneshot::channel();
+    let (future, abort_handle) = abortable(future);
+
+    spawn_local(async move {
+        if let Ok(result) = future.await {
+            let _ = result_tx.send(result);
+        }
+
+        on_complete();
+    });
+
+    TaskHandle {
+        abort_handle,
+        result: result_rx,
+    }
+}
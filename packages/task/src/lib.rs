@@ -6,17 +6,37 @@ use futures_signals::{
 };
 use silkenweb_macros::cfg_browser;
 
+mod group;
+mod resource;
+
+#[cfg(feature = "worker")]
+pub mod worker;
+
+pub use group::{spawn_abortable, TaskGroup, TaskHandle};
+pub use resource::{Resource, ResourceState};
+
 #[cfg_browser(false)]
 /// Server only task tools.
 pub mod server {
     use std::{
-        pin::pin,
-        sync::Arc,
+        error::Error,
+        fmt,
+        pin::{pin, Pin},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
         task::{Context, Poll, Wake},
+        thread,
     };
 
     use crossbeam::sync::{Parker, Unparker};
-    use futures::Future;
+    use futures::{
+        channel::{mpsc, oneshot},
+        future::{AbortHandle, Abortable},
+        Future, StreamExt,
+    };
+    use tokio::{runtime, task::LocalSet};
 
     /// Synchronous version of [`run_tasks`][super::run_tasks].
     ///
@@ -60,6 +80,119 @@ pub mod server {
             }
         }
     }
+
+    type Job = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()>>> + Send>;
+
+    /// A pool of threads, each running a single threaded executor.
+    ///
+    /// Silkenweb is single threaded, so futures that render pages aren't
+    /// `Send`. This pool lets you render pages on many threads from a
+    /// multi-threaded runtime, like an `axum` server.
+    pub struct LocalExecutorPool {
+        workers: Vec<mpsc::UnboundedSender<Job>>,
+        next_worker: AtomicUsize,
+    }
+
+    impl LocalExecutorPool {
+        /// Create a pool with `thread_count` threads.
+        ///
+        /// # Panics
+        ///
+        /// If `thread_count` is 0, or a thread can't be spawned.
+        pub fn new(thread_count: usize) -> Self {
+            assert!(thread_count > 0, "A pool must have at least 1 thread");
+
+            let workers = (0..thread_count)
+                .map(|index| {
+                    let (job_tx, job_rx) = mpsc::unbounded();
+
+                    thread::Builder::new()
+                        .name(format!("silkenweb-executor-{index}"))
+                        .spawn(move || run_worker(job_rx))
+                        .expect("Couldn't spawn executor thread");
+
+                    job_tx
+                })
+                .collect();
+
+            Self {
+                workers,
+                next_worker: AtomicUsize::new(0),
+            }
+        }
+
+        /// Run the future returned by `f` on one of the pool's threads.
+        ///
+        /// `f` is called on the pool thread, so the future it returns doesn't
+        /// need to be `Send`. The returned future is `Send`, and resolves to
+        /// the output of the future returned by `f`. Jobs are dispatched to
+        /// threads in round robin order.
+        ///
+        /// Dropping the returned future aborts the task on the pool thread.
+        pub fn spawn_pinned<F, Fut>(
+            &self,
+            f: F,
+        ) -> impl Future<Output = Result<Fut::Output, TaskPanicked>> + Send + 'static
+        where
+            F: FnOnce() -> Fut + Send + 'static,
+            Fut: Future + 'static,
+            Fut::Output: Send + 'static,
+        {
+            let (result_tx, result_rx) = oneshot::channel();
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            let job: Job = Box::new(move || {
+                Box::pin(async move {
+                    if let Ok(output) = Abortable::new(f(), abort_registration).await {
+                        let _ = result_tx.send(output);
+                    }
+                })
+            });
+            let worker = self.next_worker.fetch_add(1, Ordering::Relaxed) % self.workers.len();
+            self.workers[worker]
+                .unbounded_send(job)
+                .expect("Executor threads should run until the pool is dropped");
+            let abort_on_drop = AbortOnDrop(abort_handle);
+
+            async move {
+                let result = result_rx.await.map_err(|_| TaskPanicked);
+                drop(abort_on_drop);
+                result
+            }
+        }
+    }
+
+    struct AbortOnDrop(AbortHandle);
+
+    impl Drop for AbortOnDrop {
+        fn drop(&mut self) {
+            self.0.abort();
+        }
+    }
+
+    fn run_worker(mut jobs: mpsc::UnboundedReceiver<Job>) {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Couldn't build executor runtime");
+
+        LocalSet::new().block_on(&runtime, async move {
+            while let Some(job) = jobs.next().await {
+                tokio::task::spawn_local(job());
+            }
+        });
+    }
+
+    /// A task on a [`LocalExecutorPool`] panicked before it completed.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct TaskPanicked;
+
+    impl fmt::Display for TaskPanicked {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Task panicked")
+        }
+    }
+
+    impl Error for TaskPanicked {}
 }
 
 #[cfg_browser(false)]
@@ -183,6 +316,8 @@ pub use arch::scope;
 pub use arch::sync_scope;
 
 /// Spawn a future on the microtask queue.
+///
+/// Use [`spawn_abortable`] or a [`TaskGroup`] if you need to cancel the task.
 pub fn spawn_local<F>(future: F)
 where
     F: Future<Output = ()> + 'static,
@@ -0,0 +1,204 @@
+use std::{cell::RefCell, rc::Rc};
+
+use clonelet::clone;
+use futures::{
+    future::{abortable, AbortHandle},
+    Future,
+};
+use futures_signals::{
+    map_ref,
+    signal::{Mutable, Signal, SignalExt},
+};
+
+use crate::spawn_local;
+
+/// The state of a [`Resource`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceState<T, E> {
+    /// The resource is being fetched.
+    Loading,
+    /// The resource was fetched successfully.
+    Ready(T),
+    /// Fetching the resource failed.
+    Failed(E),
+}
+
+impl<T, E> ResourceState<T, E> {
+    /// Is the resource being fetched?
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading)
+    }
+
+    /// Get the value, if the resource is ready.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Self::Ready(value) => Some(value),
+            Self::Loading | Self::Failed(_) => None,
+        }
+    }
+
+    /// Get the error, if fetching failed.
+    pub fn failed(&self) -> Option<&E> {
+        match self {
+            Self::Failed(error) => Some(error),
+            Self::Loading | Self::Ready(_) => None,
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for ResourceState<T, E> {
+    fn from(value: Result<T, E>) -> Self {
+        match value {
+            Ok(value) => Self::Ready(value),
+            Err(error) => Self::Failed(error),
+        }
+    }
+}
+
+/// An asynchronous value derived from a signal.
+///
+/// Each time the input signal changes, the resource is fetched again using
+/// the latest input. Any fetch that's still in progress is cancelled, so the
+/// state will never be updated by a stale request.
+///
+/// Cloning a `Resource` gives another handle to the same underlying state.
+/// Fetching stops when all handles are dropped.
+///
+/// # Example
+///
+/// ```rust
+/// # use futures_signals::signal::Mutable;
+/// # use silkenweb_task::{
+/// #     server::run_tasks_sync, sync_scope, Resource, ResourceState, TaskSignal,
+/// # };
+/// #
+/// let user_id = Mutable::new(1);
+///
+/// // A scope isn't required on browser platforms
+/// sync_scope(|| {
+///     let user = Resource::new(user_id.signal(), |id| async move {
+///         Ok::<_, String>(format!("User {id}"))
+///     });
+///     let state = user.signal().to_mutable();
+///     assert_eq!(state.get_cloned(), ResourceState::Loading);
+///
+///     run_tasks_sync();
+///     assert_eq!(state.get_cloned(), ResourceState::Ready("User 1".to_owned()));
+///
+///     user_id.set(2);
+///     run_tasks_sync();
+///     assert_eq!(state.get_cloned(), ResourceState::Ready("User 2".to_owned()));
+/// });
+/// ```
+pub struct Resource<T, E> {
+    state: Mutable<ResourceState<T, E>>,
+    generation: Mutable<u64>,
+    _driver: Rc<AbortOnDrop>,
+}
+
+impl<T: 'static, E: 'static> Resource<T, E> {
+    /// Create a new resource.
+    ///
+    /// `fetch` is called with each value of `input`. The resource is initially
+    /// [`ResourceState::Loading`].
+    ///
+    /// The future to drive the resource is spawned on the microtask queue.
+    pub fn new<Sig, F, Fut>(input: Sig, mut fetch: F) -> Self
+    where
+        Sig: Signal + 'static,
+        Sig::Item: Clone + 'static,
+        F: FnMut(Sig::Item) -> Fut + 'static,
+        Fut: Future<Output = Result<T, E>> + 'static,
+    {
+        let state = Mutable::new(ResourceState::Loading);
+        let generation = Mutable::new(0);
+        let in_flight = Rc::new(RefCell::new(None::<AbortHandle>));
+
+        let latest_input = map_ref! {
+            let input = input,
+            let _generation = generation.signal() => input.clone()
+        };
+
+        let (driver, driver_handle) = abortable(latest_input.for_each({
+            clone!(state, in_flight);
+
+            move |input| {
+                if let Some(stale) = in_flight.take() {
+                    stale.abort();
+                }
+
+                state.set(ResourceState::Loading);
+                let (fetch_future, fetch_handle) = abortable(fetch(input));
+                in_flight.replace(Some(fetch_handle));
+
+                spawn_local({
+                    clone!(state);
+
+                    async move {
+                        if let Ok(result) = fetch_future.await {
+                            state.set(result.into());
+                        }
+                    }
+                });
+
+                async {}
+            }
+        }));
+
+        spawn_local(async move {
+            let _ = driver.await;
+
+            if let Some(stale) = in_flight.take() {
+                stale.abort();
+            }
+        });
+
+        Self {
+            state,
+            generation,
+            _driver: Rc::new(AbortOnDrop(driver_handle)),
+        }
+    }
+
+    /// Fetch the resource again, using the latest input.
+    pub fn refetch(&self) {
+        self.generation
+            .replace_with(|generation| generation.wrapping_add(1));
+    }
+
+    /// A signal of the resource state.
+    pub fn signal(&self) -> impl Signal<Item = ResourceState<T, E>> + 'static
+    where
+        T: Clone,
+        E: Clone,
+    {
+        self.state.signal_cloned()
+    }
+
+    /// A signal of the resource state, mapped by reference.
+    pub fn signal_ref<B, F>(&self, f: F) -> impl Signal<Item = B> + 'static
+    where
+        B: 'static,
+        F: FnMut(&ResourceState<T, E>) -> B + 'static,
+    {
+        self.state.signal_ref(f)
+    }
+}
+
+impl<T, E> Clone for Resource<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            generation: self.generation.clone(),
+            _driver: self._driver.clone(),
+        }
+    }
+}
+
+struct AbortOnDrop(AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
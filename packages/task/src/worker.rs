@@ -0,0 +1,366 @@
+//! Run tasks in a [Web Worker], and talk to them with typed channels.
+//!
+//! Messages are encoded with [`serde_json`], so inputs and outputs must
+//! implement [`Serialize`] and [`DeserializeOwned`].
+//!
+//! In the browser, [`Worker::spawn`] starts a Web Worker from a script. The
+//! script should call [`run_in_worker`] with the task to run. On the server, or
+//! for testing, [`Worker::spawn_local`] runs the task on the current thread's
+//! microtask queue, using the same message encoding.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use futures::StreamExt;
+//! # use silkenweb_task::{server::run_tasks_sync, sync_scope, worker::Worker};
+//! #
+//! // A scope isn't required on browser platforms
+//! sync_scope(|| {
+//!     let mut worker = Worker::<u64, u64>::spawn_local(|mut inputs, outputs| async move {
+//!         while let Some(x) = inputs.next().await {
+//!             if let Ok(x) = x {
+//!                 outputs.send(&(x * x));
+//!             }
+//!         }
+//!     });
+//!
+//!     worker.send(&3);
+//!     run_tasks_sync();
+//!     assert_eq!(worker.next_now(), Some(Ok(9)));
+//! });
+//! ```
+//!
+//! [Web Worker]: https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API
+use std::{
+    error::Error,
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use futures::{channel::mpsc, Future, FutureExt, Stream, StreamExt};
+use futures_signals::signal::{self, Signal};
+use serde::{de::DeserializeOwned, Serialize};
+use silkenweb_macros::cfg_browser;
+
+use crate::spawn_abortable;
+
+/// The sending half of a worker channel.
+pub struct Sender<T> {
+    post: Rc<dyn Fn(String)>,
+    phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize> Sender<T> {
+    fn new(post: impl Fn(String) + 'static) -> Self {
+        Self {
+            post: Rc::new(post),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Send a message.
+    ///
+    /// Messages are silently dropped if the other end of the channel has been
+    /// dropped.
+    ///
+    /// # Panics
+    ///
+    /// If `message` can't be serialized.
+    pub fn send(&self, message: &T) {
+        (self.post)(serde_json::to_string(message).expect("Couldn't encode worker message"));
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            post: self.post.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The receiving half of a worker channel.
+///
+/// Messages are external input, so each item is a [`Result`]. A message that
+/// can't be decoded yields a [`MessageError`], and the stream carries on with
+/// the next message.
+pub struct Receiver<T> {
+    messages: mpsc::UnboundedReceiver<RawMessage>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Receiver<T> {
+    fn new(messages: mpsc::UnboundedReceiver<RawMessage>) -> Self {
+        Self {
+            messages,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the next message, if one is available now.
+    pub fn next_now(&mut self) -> Option<Result<T, MessageError>> {
+        self.next().now_or_never().flatten()
+    }
+
+    /// A signal of the latest message.
+    ///
+    /// The signal is `None` until the first message arrives.
+    pub fn signal(self) -> impl Signal<Item = Option<Result<T, MessageError>>>
+    where
+        T: 'static,
+    {
+        signal::from_stream(self)
+    }
+}
+
+impl<T: DeserializeOwned> Stream for Receiver<T> {
+    type Item = Result<T, MessageError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.messages.poll_next_unpin(cx).map(|message| {
+            message.map(|message| {
+                serde_json::from_str(&message?).map_err(|e| MessageError::Decode(e.to_string()))
+            })
+        })
+    }
+}
+
+/// A worker message that couldn't be received.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MessageError {
+    /// The message wasn't a string, so it wasn't sent by a [`Sender`].
+    NotText,
+    /// The message couldn't be decoded.
+    Decode(String),
+}
+
+impl fmt::Display for MessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotText => f.write_str("Worker message wasn't a string"),
+            Self::Decode(e) => write!(f, "Couldn't decode worker message: {e}"),
+        }
+    }
+}
+
+impl Error for MessageError {}
+
+type RawMessage = Result<String, MessageError>;
+
+/// A handle to a worker task.
+///
+/// Send inputs to the worker with [`Worker::send`], and receive outputs by
+/// using the worker as a [`Stream`]. The worker is terminated when the handle
+/// is dropped.
+pub struct Worker<In, Out> {
+    sender: Sender<In>,
+    receiver: Receiver<Out>,
+    _terminate: Terminate,
+}
+
+impl<In, Out> Worker<In, Out>
+where
+    In: Serialize + 'static,
+    Out: DeserializeOwned + 'static,
+{
+    /// Run `task` on the current thread.
+    ///
+    /// The task is spawned on the microtask queue, and is passed the
+    /// receiving half of the input channel and the sending half of the output
+    /// channel. Messages are encoded just as they would be for a Web Worker.
+    ///
+    /// This is useful as a fallback when Web Workers aren't available, for
+    /// example on the server or in native tests.
+    pub fn spawn_local<F, Fut>(task: F) -> Self
+    where
+        In: DeserializeOwned,
+        Out: Serialize,
+        F: FnOnce(Receiver<In>, Sender<Out>) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let (input_tx, input_rx) = local_channel();
+        let (output_tx, output_rx) = local_channel();
+        let task = spawn_abortable(task(input_rx, output_tx));
+
+        Self {
+            sender: input_tx,
+            receiver: output_rx,
+            _terminate: Terminate(Box::new(move || task.abort())),
+        }
+    }
+
+    /// Send a message to the worker.
+    pub fn send(&self, message: &In) {
+        self.sender.send(message);
+    }
+
+    /// Get a [`Sender`] to send messages to the worker.
+    ///
+    /// Messages are dropped once the worker has been terminated.
+    pub fn sender(&self) -> Sender<In> {
+        self.sender.clone()
+    }
+
+    /// Get the next output, if one is available now.
+    pub fn next_now(&mut self) -> Option<Result<Out, MessageError>> {
+        self.receiver.next_now()
+    }
+
+    /// A signal of the latest output.
+    ///
+    /// The signal is `None` until the first output arrives. The worker will
+    /// run until the signal is dropped.
+    pub fn signal(self) -> impl Signal<Item = Option<Result<Out, MessageError>>> {
+        signal::from_stream(self)
+    }
+}
+
+#[cfg_browser(true)]
+impl<In, Out> Worker<In, Out>
+where
+    In: Serialize + 'static,
+    Out: DeserializeOwned + 'static,
+{
+    /// Start a Web Worker from `script_url`.
+    ///
+    /// The script should call [`run_in_worker`] to run a task with matching
+    /// input and output types.
+    ///
+    /// This is only available in the browser.
+    pub fn spawn(script_url: &str) -> Result<Self, wasm_bindgen::JsValue> {
+        let (worker, messages, on_message) = arch::spawn_worker(script_url)?;
+        let sender = Sender::new({
+            let worker = worker.clone();
+            move |message| arch::post_message(&worker, &message)
+        });
+
+        Ok(Self {
+            sender,
+            receiver: Receiver::new(messages),
+            _terminate: Terminate(Box::new(move || {
+                worker.terminate();
+                // Keep the message handler alive as long as the worker.
+                let _ = &on_message;
+            })),
+        })
+    }
+}
+
+impl<In, Out: DeserializeOwned> Stream for Worker<In, Out> {
+    type Item = Result<Out, MessageError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+/// Run `task` as the body of a Web Worker.
+///
+/// Call this from the worker's entry point. `task` is passed the receiving half
+/// of the input channel and the sending half of the output channel, and is
+/// spawned on the worker's microtask queue.
+///
+/// This is only available in the browser.
+#[cfg_browser(true)]
+pub fn run_in_worker<In, Out, F, Fut>(task: F)
+where
+    In: DeserializeOwned + 'static,
+    Out: Serialize + 'static,
+    F: FnOnce(Receiver<In>, Sender<Out>) -> Fut,
+    Fut: Future<Output = ()> + 'static,
+{
+    let (scope, messages) = arch::worker_scope();
+    let sender = Sender::new(move |message| arch::post_message(&scope, &message));
+
+    crate::spawn_local(task(Receiver::new(messages), sender));
+}
+
+struct Terminate(Box<dyn FnMut()>);
+
+impl Drop for Terminate {
+    fn drop(&mut self) {
+        (self.0)()
+    }
+}
+
+fn local_channel<T>() -> (Sender<T>, Receiver<T>)
+where
+    T: Serialize + DeserializeOwned,
+{
+    let (message_tx, message_rx) = mpsc::unbounded();
+    let sender = Sender::new(move |message| {
+        let _ = message_tx.unbounded_send(Ok(message));
+    });
+
+    (sender, Receiver::new(message_rx))
+}
+
+#[cfg_browser(true)]
+mod arch {
+    use futures::channel::mpsc;
+    use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
+    use web_sys::{DedicatedWorkerGlobalScope, MessageEvent, Worker};
+
+    use super::{MessageError, RawMessage};
+
+    pub type OnMessage = Closure<dyn FnMut(MessageEvent)>;
+
+    pub fn spawn_worker(
+        script_url: &str,
+    ) -> Result<(Worker, mpsc::UnboundedReceiver<RawMessage>, OnMessage), JsValue> {
+        let worker = Worker::new(script_url)?;
+        let (messages, on_message) = on_message();
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        Ok((worker, messages, on_message))
+    }
+
+    pub fn worker_scope() -> (
+        DedicatedWorkerGlobalScope,
+        mpsc::UnboundedReceiver<RawMessage>,
+    ) {
+        let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+        let (messages, on_message) = on_message();
+        scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        // The worker's message handler lives as long as the worker.
+        on_message.forget();
+
+        (scope, messages)
+    }
+
+    pub fn post_message(target: &impl PostMessage, message: &str) {
+        target
+            .post_message(&JsValue::from_str(message))
+            .unwrap_throw();
+    }
+
+    pub trait PostMessage {
+        fn post_message(&self, message: &JsValue) -> Result<(), JsValue>;
+    }
+
+    impl PostMessage for Worker {
+        fn post_message(&self, message: &JsValue) -> Result<(), JsValue> {
+            Worker::post_message(self, message)
+        }
+    }
+
+    impl PostMessage for DedicatedWorkerGlobalScope {
+        fn post_message(&self, message: &JsValue) -> Result<(), JsValue> {
+            DedicatedWorkerGlobalScope::post_message(self, message)
+        }
+    }
+
+    fn on_message() -> (mpsc::UnboundedReceiver<RawMessage>, OnMessage) {
+        let (message_tx, message_rx) = mpsc::unbounded();
+        let on_message = Closure::wrap(Box::new(move |event: MessageEvent| {
+            let message = event.data().as_string().ok_or(MessageError::NotText);
+            let _ = message_tx.unbounded_send(message);
+        }) as Box<dyn FnMut(MessageEvent)>);
+
+        (message_rx, on_message)
+    }
+}
@@ -0,0 +1,65 @@
+#![cfg(not(target_arch = "wasm32"))]
+use std::{sync::mpsc, thread, time::Duration};
+
+use futures::future;
+use silkenweb_task::server::{block_on, LocalExecutorPool, TaskPanicked};
+
+#[test]
+fn round_robin() {
+    let pool = LocalExecutorPool::new(2);
+    let thread_names: Vec<String> = (0..4)
+        .map(|_| {
+            block_on(pool.spawn_pinned(|| async { thread::current().name().unwrap().to_string() }))
+                .unwrap()
+        })
+        .collect();
+
+    assert_eq!(
+        thread_names,
+        [
+            "silkenweb-executor-0",
+            "silkenweb-executor-1",
+            "silkenweb-executor-0",
+            "silkenweb-executor-1"
+        ]
+    );
+}
+
+#[test]
+fn panic() {
+    let pool = LocalExecutorPool::new(1);
+    let result = block_on(pool.spawn_pinned(|| async { panic!("Render failed") }));
+    assert_eq!(result, Err::<(), _>(TaskPanicked));
+
+    let result = block_on(pool.spawn_pinned(|| async { 1 }));
+    assert_eq!(result, Ok(1), "The pool thread survives a panic");
+}
+
+#[test]
+fn cancel() {
+    let pool = LocalExecutorPool::new(1);
+    let (started_tx, started_rx) = mpsc::channel();
+    let (dropped_tx, dropped_rx) = mpsc::channel();
+
+    let task = pool.spawn_pinned(move || async move {
+        let _guard = OnDrop(dropped_tx);
+        started_tx.send(()).unwrap();
+        future::pending::<()>().await
+    });
+
+    started_rx.recv_timeout(TIMEOUT).unwrap();
+    drop(task);
+    dropped_rx
+        .recv_timeout(TIMEOUT)
+        .expect("The task should be dropped on the pool thread");
+}
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+struct OnDrop(mpsc::Sender<()>);
+
+impl Drop for OnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.send(());
+    }
+}
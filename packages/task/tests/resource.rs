@@ -0,0 +1,148 @@
+#![cfg(not(target_arch = "wasm32"))]
+use std::{cell::RefCell, rc::Rc};
+
+use futures::channel::oneshot;
+use futures_signals::signal::Mutable;
+use silkenweb_task::{server::run_tasks_sync, sync_scope, Resource, ResourceState, TaskSignal};
+
+type Replies = Rc<RefCell<Vec<(u32, oneshot::Sender<Result<String, String>>)>>>;
+
+/// A resource where each fetch waits for a reply to be sent on the channel in
+/// `replies`.
+fn resource(input: &Mutable<u32>) -> (Resource<String, String>, Replies) {
+    let replies = Replies::default();
+    let resource = Resource::new(input.signal(), {
+        let replies = replies.clone();
+
+        move |id| {
+            let (send, receive) = oneshot::channel();
+            replies.borrow_mut().push((id, send));
+            async move {
+                receive
+                    .await
+                    .unwrap_or_else(|_| Err("Cancelled".to_string()))
+            }
+        }
+    });
+
+    (resource, replies)
+}
+
+fn reply(replies: &Replies, index: usize, value: Result<&str, &str>) -> bool {
+    let (_id, send) = replies.borrow_mut().remove(index);
+    send.send(value.map(str::to_string).map_err(str::to_string))
+        .is_ok()
+}
+
+#[test]
+fn ready() {
+    sync_scope(|| {
+        let input = Mutable::new(1);
+        let (resource, replies) = resource(&input);
+        let state = resource.signal().to_mutable();
+        run_tasks_sync();
+        assert_eq!(state.get_cloned(), ResourceState::Loading);
+        assert_eq!(replies.borrow()[0].0, 1);
+
+        assert!(reply(&replies, 0, Ok("User 1")));
+        run_tasks_sync();
+        assert_eq!(
+            state.get_cloned(),
+            ResourceState::Ready("User 1".to_string())
+        );
+    })
+}
+
+#[test]
+fn failed() {
+    sync_scope(|| {
+        let input = Mutable::new(1);
+        let (resource, replies) = resource(&input);
+        let state = resource.signal().to_mutable();
+        run_tasks_sync();
+
+        assert!(reply(&replies, 0, Err("Not found")));
+        run_tasks_sync();
+        assert_eq!(
+            state.get_cloned(),
+            ResourceState::Failed("Not found".to_string())
+        );
+        assert_eq!(
+            state.get_cloned().failed().map(String::as_str),
+            Some("Not found")
+        );
+    })
+}
+
+#[test]
+fn stale_fetch_cancelled() {
+    sync_scope(|| {
+        let input = Mutable::new(1);
+        let (resource, replies) = resource(&input);
+        let state = resource.signal().to_mutable();
+        run_tasks_sync();
+
+        input.set(2);
+        run_tasks_sync();
+        assert_eq!(replies.borrow().len(), 2);
+        assert_eq!(replies.borrow()[1].0, 2);
+
+        assert!(
+            !reply(&replies, 0, Ok("User 1")),
+            "The stale fetch was dropped"
+        );
+        run_tasks_sync();
+        assert_eq!(state.get_cloned(), ResourceState::Loading);
+
+        assert!(reply(&replies, 0, Ok("User 2")));
+        run_tasks_sync();
+        assert_eq!(
+            state.get_cloned(),
+            ResourceState::Ready("User 2".to_string())
+        );
+    })
+}
+
+#[test]
+fn refetch() {
+    sync_scope(|| {
+        let input = Mutable::new(1);
+        let (resource, replies) = resource(&input);
+        let state = resource.signal().to_mutable();
+        run_tasks_sync();
+        assert!(reply(&replies, 0, Ok("First")));
+        run_tasks_sync();
+        assert_eq!(
+            state.get_cloned(),
+            ResourceState::Ready("First".to_string())
+        );
+
+        resource.refetch();
+        run_tasks_sync();
+        assert_eq!(state.get_cloned(), ResourceState::Loading);
+        assert_eq!(replies.borrow()[0].0, 1, "Refetch uses the latest input");
+
+        assert!(reply(&replies, 0, Ok("Second")));
+        run_tasks_sync();
+        assert_eq!(
+            state.get_cloned(),
+            ResourceState::Ready("Second".to_string())
+        );
+    })
+}
+
+#[test]
+fn drop_cancels_fetch() {
+    sync_scope(|| {
+        let input = Mutable::new(1);
+        let (resource, replies) = resource(&input);
+        run_tasks_sync();
+
+        drop(resource);
+        run_tasks_sync();
+        assert!(
+            !reply(&replies, 0, Ok("User 1")),
+            "The fetch was dropped with the resource"
+        );
+    })
+}
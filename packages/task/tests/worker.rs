@@ -0,0 +1,112 @@
+#![cfg(all(feature = "worker", not(target_arch = "wasm32")))]
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use silkenweb_task::{
+    server::run_tasks_sync,
+    sync_scope,
+    worker::{MessageError, Worker},
+};
+
+/// An even number.
+///
+/// Any number can be serialized, but only even numbers can be deserialized, so
+/// we can send messages that can't be decoded.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u32")]
+struct Even(u32);
+
+impl TryFrom<u32> for Even {
+    type Error = String;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if value % 2 == 0 {
+            Ok(Self(value))
+        } else {
+            Err(format!("{value} is odd"))
+        }
+    }
+}
+
+#[test]
+fn round_trip() {
+    sync_scope(|| {
+        let mut worker = Worker::<u32, u32>::spawn_local(|mut inputs, outputs| async move {
+            while let Some(x) = inputs.next().await {
+                outputs.send(&(x.unwrap() * 2));
+            }
+        });
+        assert_eq!(worker.next_now(), None);
+
+        worker.send(&1);
+        worker.sender().send(&2);
+        run_tasks_sync();
+        assert_eq!(worker.next_now(), Some(Ok(2)));
+        assert_eq!(worker.next_now(), Some(Ok(4)));
+        assert_eq!(worker.next_now(), None);
+    })
+}
+
+#[test]
+fn malformed_input() {
+    sync_scope(|| {
+        let mut worker = Worker::<Even, String>::spawn_local(|mut inputs, outputs| async move {
+            while let Some(x) = inputs.next().await {
+                outputs.send(&match x {
+                    Ok(Even(x)) => format!("Even {x}"),
+                    Err(MessageError::Decode(_)) => "Decode error".to_string(),
+                    Err(MessageError::NotText) => "Not text".to_string(),
+                });
+            }
+        });
+
+        worker.send(&Even(1));
+        worker.send(&Even(2));
+        run_tasks_sync();
+        assert_eq!(worker.next_now(), Some(Ok("Decode error".to_string())));
+        assert_eq!(
+            worker.next_now(),
+            Some(Ok("Even 2".to_string())),
+            "The worker carries on after a malformed message"
+        );
+    })
+}
+
+#[test]
+fn malformed_output() {
+    sync_scope(|| {
+        let mut worker = Worker::<u32, Even>::spawn_local(|mut inputs, outputs| async move {
+            while let Some(x) = inputs.next().await {
+                outputs.send(&Even(x.unwrap()));
+            }
+        });
+
+        worker.send(&1);
+        worker.send(&2);
+        run_tasks_sync();
+        assert!(matches!(
+            worker.next_now(),
+            Some(Err(MessageError::Decode(_)))
+        ));
+        assert_eq!(worker.next_now(), Some(Ok(Even(2))));
+    })
+}
+
+#[test]
+fn terminate_on_drop() {
+    sync_scope(|| {
+        let worker = Worker::<u32, u32>::spawn_local(|mut inputs, outputs| async move {
+            while let Some(x) = inputs.next().await {
+                outputs.send(&x.unwrap());
+            }
+        });
+        let sender = worker.sender();
+
+        drop(worker);
+        run_tasks_sync();
+
+        // The worker task has been aborted, so this message is dropped rather
+        // than causing a panic.
+        sender.send(&1);
+        run_tasks_sync();
+    })
+}
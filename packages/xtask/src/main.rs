@@ -197,18 +197,27 @@ fn ci_native(name: &str, workspace_dir: Option<&str>, platform: Platform) -> Tas
 }
 
 fn test_features(mut tasks: Tasks) -> Tasks {
-    for features in ["declarative-shadow-dom"].into_iter().powerset() {
+    for features in ["declarative-shadow-dom", "htmx", "worker"]
+        .into_iter()
+        .powerset()
+    {
         if !features.is_empty() {
+            let features = features.join(",");
             tasks.add_run({
                 clone!(features);
-                cmd!( "cargo clippy --features {features...} --all-targets -- -D warnings -D clippy::all" )
+                cmd!( "cargo clippy --features {features} --all-targets -- -D warnings -D clippy::all" )
             });
-            tasks.add_run(cmd!(
-                "cargo test --package silkenweb --features {features...}"
-            ));
+            tasks.add_run(cmd!("cargo test --package silkenweb --features {features}"));
         }
     }
 
+    for (package, feature) in [
+        ("silkenweb-task", "worker"),
+        ("silkenweb-parse", "markdown"),
+    ] {
+        tasks.add_run(cmd!("cargo test --package {package} --features {feature}"));
+    }
+
     tasks
 }
 